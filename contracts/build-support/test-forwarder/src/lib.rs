@@ -0,0 +1,60 @@
+//! Test forwarder
+//!
+//! Relays a call to another alkane so the callee sees this contract as its caller instead of 0:0.
+//! Only built for the contract tests, never deployed.
+
+use alkanes_runtime::{declare_alkane, message::MessageDispatch, runtime::AlkaneResponder};
+use alkanes_support::{cellpack::Cellpack, id::AlkaneId, response::CallResponse};
+use anyhow::{anyhow, ensure, Result};
+use metashrew_support::compat::to_arraybuffer_layout;
+
+#[derive(Default)]
+pub struct TestForwarder(());
+
+#[derive(MessageDispatch)]
+enum TestForwarderMessage {
+    #[opcode(0)]
+    Initialize,
+
+    //Inputs after the opcode are the target block, the target tx and the callee's own inputs
+    #[opcode(1)]
+    Forward,
+}
+
+impl TestForwarder {
+    fn initialize(&self) -> Result<CallResponse> {
+        self.observe_initialization()
+            .map_err(|_| anyhow!("Contract already initialized"))?;
+
+        let context = self.context()?;
+        Ok(CallResponse::forward(&context.incoming_alkanes))
+    }
+
+    //Incoming alkanes go along with the call, whatever the callee returns comes back out
+    fn forward(&self) -> Result<CallResponse> {
+        let context = self.context()?;
+
+        ensure!(
+            context.inputs.len() >= 4,
+            "FORWARDER: expected a target and an opcode"
+        );
+
+        let cellpack = Cellpack {
+            target: AlkaneId {
+                block: context.inputs[1],
+                tx: context.inputs[2],
+            },
+            inputs: context.inputs[3..].to_vec(),
+        };
+
+        self.call(&cellpack, &context.incoming_alkanes, self.fuel())
+    }
+}
+
+impl AlkaneResponder for TestForwarder {}
+
+declare_alkane! {
+    impl AlkaneResponder for TestForwarder {
+        type Message = TestForwarderMessage;
+    }
+}
@@ -0,0 +1,106 @@
+// Shared by the contract build scripts through include!, expects PACKAGE_NAME, TEST_FIXTURES and
+// the anyhow/std imports of the including build.rs.
+//
+// Native tests load the contract through src/tests/std (gitignored, see the root .gitignore),
+// same layout the alkanes-rs templates use. Fixture contracts the tests deploy next to it live
+// in build-support, "test_forwarder" is the package in build-support/test-forwarder.
+
+fn test_bindings_dir() -> Result<PathBuf> {
+    Ok(PathBuf::from(env::var("CARGO_MANIFEST_DIR")?)
+        .join("src")
+        .join("tests")
+        .join("std"))
+}
+
+fn write_test_bindings_source(modules: &[(&str, String)]) -> Result<()> {
+    let std_dir = test_bindings_dir()?;
+    fs::create_dir_all(&std_dir)?;
+
+    let mod_rs: String = modules
+        .iter()
+        .map(|(name, _)| format!("pub mod {name}_build;\n"))
+        .collect();
+    fs::write(std_dir.join("mod.rs"), mod_rs)?;
+
+    for (name, body) in modules {
+        fs::write(
+            std_dir.join(format!("{name}_build.rs")),
+            format!("pub fn get_bytes() -> Vec<u8> {{\n    {body}\n}}\n"),
+        )
+        .with_context(|| format!("writing test bindings for {name}"))?;
+    }
+
+    Ok(())
+}
+
+fn build_test_fixtures(target_dir: &Path) -> Result<Vec<(&'static str, PathBuf)>> {
+    let build_support = PathBuf::from(env::var("CARGO_MANIFEST_DIR")?)
+        .join("..")
+        .join("build-support");
+
+    TEST_FIXTURES
+        .iter()
+        .map(|&fixture| {
+            let fixture_dir = build_support.join(fixture.replace('_', "-"));
+            println!(
+                "cargo:rerun-if-changed={}",
+                fixture_dir.join("src").display()
+            );
+
+            let status = Command::new("cargo")
+                .env("CARGO_TARGET_DIR", target_dir)
+                .arg("build")
+                .arg("--release")
+                .arg("--target")
+                .arg("wasm32-unknown-unknown")
+                .arg("--manifest-path")
+                .arg(fixture_dir.join("Cargo.toml"))
+                .stdout(Stdio::inherit())
+                .stderr(Stdio::inherit())
+                .status()
+                .with_context(|| format!("failed to spawn cargo for {fixture}"))?;
+
+            if !status.success() {
+                bail!("cargo build of {fixture} exited with {}", status);
+            }
+
+            Ok((
+                fixture,
+                target_dir
+                    .join("wasm32-unknown-unknown")
+                    .join("release")
+                    .join(format!("{fixture}.wasm")),
+            ))
+        })
+        .collect()
+}
+
+fn write_test_bindings(artefact: &Path, fixtures: &[(&str, PathBuf)]) -> Result<()> {
+    let include =
+        |path: &Path| format!("include_bytes!({:?}).to_vec()", path.display().to_string());
+
+    let mut modules = vec![(PACKAGE_NAME, include(artefact))];
+    modules.extend(fixtures.iter().map(|(name, path)| (*name, include(path))));
+
+    write_test_bindings_source(&modules)
+}
+
+// No wasm was built, so `pub mod std;` only needs something to resolve. Real bindings from an
+// earlier full build are left alone.
+fn write_stub_test_bindings() -> Result<()> {
+    if test_bindings_dir()?
+        .join(format!("{PACKAGE_NAME}_build.rs"))
+        .exists()
+    {
+        return Ok(());
+    }
+
+    let mut modules = vec![(PACKAGE_NAME, "Vec::new()".to_string())];
+    modules.extend(
+        TEST_FIXTURES
+            .iter()
+            .map(|&fixture| (fixture, "Vec::new()".to_string())),
+    );
+
+    write_test_bindings_source(&modules)
+}
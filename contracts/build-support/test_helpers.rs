@@ -0,0 +1,160 @@
+// Shared by the contract test trees, each mounts it as `tests::shared` through #[path]. Only
+// depends on the alkanes test-utils dev-dependencies and the protostone crates every contract
+// already pulls in.
+
+use alkanes::indexer::index_block;
+use alkanes::tests::helpers::{get_last_outpoint_sheet, get_sheet_for_outpoint};
+use alkanes::view;
+use alkanes_support::cellpack::Cellpack;
+use alkanes_support::id::AlkaneId;
+use alkanes_support::trace::{Trace, TraceEvent};
+use anyhow::{anyhow, Result};
+use bitcoin::absolute::LockTime;
+use bitcoin::transaction::Version;
+use bitcoin::{
+    Address, Amount, Block, Network, OutPoint, ScriptBuf, Sequence, Transaction, TxIn, TxOut,
+    Witness,
+};
+use ordinals::Runestone;
+use protorune::test_helpers::create_block_with_coinbase_tx;
+use protorune_support::balance_sheet::{BalanceSheetOperations, ProtoruneRuneId};
+use protorune_support::protostone::{Protostone, ProtostoneEdict, Protostones};
+use std::str::FromStr;
+
+// Params reach the contract as LE u128 words after the opcode, see get_byte_array_from_inputs
+pub fn to_inputs(opcode: u128, bytes: &[u8]) -> Vec<u128> {
+    let mut inputs = vec![opcode];
+    inputs.extend(bytes.chunks(16).map(|chunk| {
+        let mut word = [0u8; 16];
+        word[..chunk.len()].copy_from_slice(chunk);
+        u128::from_le_bytes(word)
+    }));
+    inputs
+}
+
+// Balance of `id` on the last output of the block's last transaction
+pub fn alkane_balance(block: &Block, id: AlkaneId) -> Result<u128> {
+    let sheet = get_last_outpoint_sheet(block)?;
+    let rune: ProtoruneRuneId = id.into();
+    Ok(sheet.get(&rune))
+}
+
+// Same for any output of the last transaction, for calls that pay several outputs
+pub fn alkane_balance_at(block: &Block, vout: u32, id: AlkaneId) -> Result<u128> {
+    let sheet = get_sheet_for_outpoint(block, block.txdata.len() - 1, vout)?;
+    let rune: ProtoruneRuneId = id.into();
+    Ok(sheet.get(&rune))
+}
+
+// Protostones are traced from vout #3: one output, the OP_RETURN, then the shadow vouts. `protostone`
+// picks which one when the transaction carries several
+pub fn return_data_at(block: &Block, protostone: u32) -> Result<Vec<u8>> {
+    let outpoint = OutPoint {
+        txid: block.txdata.last().unwrap().compute_txid(),
+        vout: 3 + protostone,
+    };
+    let trace: Trace = view::trace(&outpoint)?.try_into()?;
+    let last_event = trace.0.lock().expect("Mutex poisoned").last().cloned();
+
+    match last_event {
+        Some(TraceEvent::ReturnContext(response)) => Ok(response.inner.data),
+        _ => Err(anyhow!("call did not return")),
+    }
+}
+
+pub fn return_data(block: &Block) -> Result<Vec<u8>> {
+    return_data_at(block, 0)
+}
+
+pub fn address_output(address: &str) -> Result<TxOut> {
+    Ok(TxOut {
+        value: Amount::from_sat(100_000_000),
+        script_pubkey: Address::from_str(address)?
+            .require_network(Network::Regtest)?
+            .script_pubkey(),
+    })
+}
+
+// Alkanes on the spent outpoints flow into the first protostone. The witness goes on the first
+// input and the OP_RETURN is appended after `outputs`, so shadow vouts start at outputs.len() + 1
+pub fn build_tx(
+    spent: Vec<OutPoint>,
+    witness: Witness,
+    outputs: Vec<TxOut>,
+    protostones: Vec<Protostone>,
+) -> Result<Transaction> {
+    let input = spent
+        .into_iter()
+        .enumerate()
+        .map(|(i, previous_output)| TxIn {
+            previous_output,
+            script_sig: ScriptBuf::new(),
+            sequence: Sequence::MAX,
+            witness: if i == 0 {
+                witness.clone()
+            } else {
+                Witness::new()
+            },
+        })
+        .collect();
+
+    let runestone = Runestone {
+        etching: None,
+        pointer: Some(0),
+        edicts: vec![],
+        mint: None,
+        protocol: Some(protostones.encipher()?),
+    };
+
+    let mut output = outputs;
+    output.push(TxOut {
+        value: Amount::ZERO,
+        script_pubkey: runestone.encipher(),
+    });
+
+    Ok(Transaction {
+        version: Version::ONE,
+        lock_time: LockTime::ZERO,
+        input,
+        output,
+    })
+}
+
+pub fn message(cellpack: Cellpack, pointer: u32) -> Protostone {
+    Protostone {
+        message: cellpack.encipher(),
+        pointer: Some(pointer),
+        refund: Some(0),
+        edicts: vec![],
+        from: None,
+        burn: None,
+        protocol_tag: 1,
+    }
+}
+
+// A protostone without a message that only moves what it was sent with edicts
+pub fn split(edicts: &[(AlkaneId, u128, u32)]) -> Protostone {
+    Protostone {
+        message: vec![],
+        pointer: Some(0),
+        refund: Some(0),
+        edicts: edicts
+            .iter()
+            .map(|(id, amount, output)| ProtostoneEdict {
+                id: (*id).into(),
+                amount: *amount,
+                output: *output as u128,
+            })
+            .collect(),
+        from: None,
+        burn: None,
+        protocol_tag: 1,
+    }
+}
+
+pub fn index_tx(tx: Transaction, height: u32) -> Result<Block> {
+    let mut block = create_block_with_coinbase_tx(height);
+    block.txdata.push(tx);
+    index_block(&block, height)?;
+    Ok(block)
+}
@@ -6,6 +6,9 @@ pub mod schemas;
 pub mod token;
 pub mod utils;
 
+#[cfg(test)]
+mod tests;
+
 use alkanes_runtime::{
     declare_alkane, message::MessageDispatch, runtime::AlkaneResponder, storage::StoragePointer,
 };
@@ -22,10 +25,17 @@ use std::io::Cursor;
 use std::sync::Arc;
 
 use crate::{
-    schemas::{SchemaAlkaneId, SchemaControlledMintInitializationParameters},
+    schemas::{
+        SchemaAlkaneId, SchemaControlledMintInitializationParameters,
+        SchemaMintExactSplitParameters, SchemaMintRateLimitView, SchemaMintRecord,
+        SchemaMintSchedule, SchemaMintScheduleView, SchemaMintSplit, SchemaMinter,
+    },
     utils::get_byte_array_from_inputs,
 };
 
+//Keeps the recent mint records (and the fuel spent summing them) bounded, 4 weeks of blocks
+const MAX_MINT_WINDOW_BLOCKS: u64 = 4032;
+
 #[derive(Default)]
 pub struct ControlledMint(());
 
@@ -48,6 +58,9 @@ impl ControlledMint {
     fn assert_owner(&self, context: &Context) -> Result<()> {
         let owner = self.get_owner_id()?;
 
+        //Renounced, or deployed straight from a transaction
+        ensure!(!owner.is_null(), "TORTILLA: Contract has no owner");
+
         ensure!(
             context.caller.block == owner.block.into() && context.caller.tx == owner.tx.into(),
             "TORTILLA: Caller is not the owner"
@@ -55,6 +68,222 @@ impl ControlledMint {
 
         Ok(())
     }
+
+    //Set by ProposeOwner, empty while no transfer is in flight
+    fn get_pending_owner_pointer(&self) -> StoragePointer {
+        StoragePointer::from_keyword("/pending_owner")
+    }
+
+    fn get_pending_owner_id(&self) -> Result<Option<SchemaAlkaneId>> {
+        let pending = (*self.get_pending_owner_pointer().get()).clone();
+        if pending.is_empty() {
+            return Ok(None);
+        }
+
+        let alkane_id = SchemaAlkaneId::try_from_slice(&pending).map_err(|_| {
+            anyhow!("TORTILLA: Failed to decode pending owner at get_pending_owner_id")
+        })?;
+        Ok(Some(alkane_id))
+    }
+
+    fn get_minters_pointer(&self) -> StoragePointer {
+        StoragePointer::from_keyword("/minters")
+    }
+
+    fn get_minter_list(&self) -> Result<Vec<SchemaMinter>> {
+        let minters = (*self.get_minters_pointer().get()).clone();
+        if minters.is_empty() {
+            return Ok(vec![]);
+        }
+
+        Vec::<SchemaMinter>::try_from_slice(&minters)
+            .map_err(|_| anyhow!("TORTILLA: Failed to decode minters at get_minter_list"))
+    }
+
+    fn set_minter_list(&self, minters: &[SchemaMinter]) -> Result<()> {
+        self.get_minters_pointer()
+            .set(Arc::new(borsh::to_vec(minters)?));
+        Ok(())
+    }
+
+    //Hands the outgoing owner's allowance to the new owner so a migrated game keeps minting and the
+    //old contract can't. Allowances add up when the new owner was already a minter
+    fn move_minter_role(&self, from: SchemaAlkaneId, to: SchemaAlkaneId) -> Result<()> {
+        let mut minters = self.get_minter_list()?;
+
+        let moved = match minters.iter().position(|minter| minter.alkane_id == from) {
+            Some(position) => minters.remove(position),
+            None => return Ok(()),
+        };
+
+        match minters.iter_mut().find(|minter| minter.alkane_id == to) {
+            Some(minter) => minter.allowance = minter.allowance.saturating_add(moved.allowance),
+            None => minters.push(SchemaMinter {
+                alkane_id: to,
+                allowance: moved.allowance,
+            }),
+        }
+
+        self.set_minter_list(&minters)
+    }
+
+    fn to_schema_alkane_id(&self, block: u128, tx: u128) -> Result<SchemaAlkaneId> {
+        Ok(SchemaAlkaneId {
+            block: block
+                .try_into()
+                .map_err(|_| anyhow!("TORTILLA: alkane block out of range"))?,
+            tx: tx
+                .try_into()
+                .map_err(|_| anyhow!("TORTILLA: alkane tx out of range"))?,
+        })
+    }
+
+    //One record per block that minted inside the current window, only kept when a rate limit is set
+    fn get_recent_mints_pointer(&self) -> StoragePointer {
+        StoragePointer::from_keyword("/recent_mints")
+    }
+
+    //Drops records that fell out of the window and returns (records, minted this block, minted in window)
+    fn get_rate_limit_usage(
+        &self,
+        consts: &SchemaControlledMintInitializationParameters,
+    ) -> Result<(Vec<SchemaMintRecord>, u128, u128)> {
+        let height = self.height();
+        let window_start = match consts.max_mint_per_window {
+            Some(window) => height.saturating_sub(window.blocks.saturating_sub(1)),
+            None => height,
+        };
+
+        let records_bytes = (*self.get_recent_mints_pointer().get()).clone();
+        let mut records = if records_bytes.is_empty() {
+            vec![]
+        } else {
+            Vec::<SchemaMintRecord>::try_from_slice(&records_bytes).map_err(|_| {
+                anyhow!("TORTILLA: Failed to decode recent mints at get_rate_limit_usage")
+            })?
+        };
+
+        records.retain(|record| record.height >= window_start);
+
+        let minted_this_block = records
+            .iter()
+            .filter(|record| record.height == height)
+            .map(|record| record.amount)
+            .sum();
+        let minted_in_window = records.iter().map(|record| record.amount).sum();
+
+        Ok((records, minted_this_block, minted_in_window))
+    }
+
+    fn enforce_rate_limits(&self, amount: u128) -> Result<()> {
+        let consts = self.get_consts();
+        if consts.max_mint_per_block.is_none() && consts.max_mint_per_window.is_none() {
+            return Ok(());
+        }
+
+        let (mut records, minted_this_block, minted_in_window) =
+            self.get_rate_limit_usage(&consts)?;
+
+        if let Some(max_per_block) = consts.max_mint_per_block {
+            ensure!(
+                minted_this_block.saturating_add(amount) <= max_per_block,
+                "TORTILLA: Mint exceeds per block limit. Requested: {amount}. Remaining this block: {}",
+                max_per_block.saturating_sub(minted_this_block)
+            );
+        }
+
+        if let Some(window) = consts.max_mint_per_window {
+            ensure!(
+                minted_in_window.saturating_add(amount) <= window.amount,
+                "TORTILLA: Mint exceeds the {} block window limit. Requested: {amount}. Remaining in window: {}",
+                window.blocks,
+                window.amount.saturating_sub(minted_in_window)
+            );
+        }
+
+        let height = self.height();
+        match records.iter_mut().find(|record| record.height == height) {
+            Some(record) => record.amount = record.amount.saturating_add(amount),
+            None => records.push(SchemaMintRecord { height, amount }),
+        }
+
+        self.get_recent_mints_pointer()
+            .set(Arc::new(borsh::to_vec(&records)?));
+
+        Ok(())
+    }
+
+    //Everything minted since initialize, only tracked when a schedule is set
+    fn get_scheduled_minted_pointer(&self) -> StoragePointer {
+        StoragePointer::from_keyword("/scheduled_minted")
+    }
+
+    fn enforce_mint_schedule(&self, amount: u128) -> Result<()> {
+        let schedule = match self.get_consts().mint_schedule {
+            Some(schedule) => schedule,
+            None => return Ok(()),
+        };
+
+        let mut ptr_minted = self.get_scheduled_minted_pointer();
+        let minted = ptr_minted.get_value::<u128>();
+        let unlocked = schedule.unlocked_at(self.height());
+
+        let minted_after = minted
+            .checked_add(amount)
+            .ok_or_else(|| anyhow!("TORTILLA: overflow while tracking scheduled mints"))?;
+
+        ensure!(
+            minted_after <= unlocked,
+            "TORTILLA: Mint exceeds the unlocked schedule. Requested: {amount}. Unlocked and unminted: {}",
+            unlocked.saturating_sub(minted)
+        );
+
+        ptr_minted.set_value::<u128>(minted_after);
+
+        Ok(())
+    }
+
+    //Sums the splits once the transaction's split protostone is known to pay each of them
+    fn get_split_total(&self, context: &Context, splits: &[SchemaMintSplit]) -> Result<u128> {
+        ensure!(
+            !splits.is_empty(),
+            "TORTILLA: At least one split is required"
+        );
+
+        let mut total: u128 = 0;
+        for split in splits {
+            ensure!(split.amount > 0, "TORTILLA: Split amounts must be non-zero");
+            total = total
+                .checked_add(split.amount)
+                .ok_or_else(|| anyhow!("TORTILLA: overflow while summing splits"))?;
+        }
+
+        self.validate_mint_split(context, splits)?;
+
+        Ok(total)
+    }
+
+    //Takes amount out of the caller's allowance, fails if the caller is not a minter
+    fn spend_allowance(&self, context: &Context, amount: u128) -> Result<()> {
+        let caller = self.to_schema_alkane_id(context.caller.block, context.caller.tx)?;
+        let mut minters = self.get_minter_list()?;
+
+        let minter = minters
+            .iter_mut()
+            .find(|minter| minter.alkane_id == caller)
+            .ok_or_else(|| anyhow!("TORTILLA: Caller is not a minter"))?;
+
+        if minter.allowance != u128::MAX {
+            minter.allowance = minter.allowance.checked_sub(amount).ok_or_else(|| {
+                anyhow!(
+                    "TORTILLA: Mint exceeds allowance. Requested: {amount}. Allowance: {}",
+                    minter.allowance
+                )
+            })?;
+        }
+
+        self.set_minter_list(&minters)
+    }
 }
 
 #[derive(MessageDispatch)]
@@ -100,6 +329,64 @@ enum ControlledMintMessage {
     #[opcode(107)]
     RenounceOwnership,
 
+    #[opcode(108)]
+    GrantMinter {
+        block: u128,
+        tx: u128,
+        allowance: u128,
+    },
+
+    #[opcode(109)]
+    RevokeMinter { block: u128, tx: u128 },
+
+    #[opcode(110)]
+    GetMinters,
+
+    #[opcode(111)]
+    #[returns(u128)]
+    GetAllowance { block: u128, tx: u128 },
+
+    #[opcode(112)]
+    ProposeOwner { block: u128, tx: u128 },
+
+    #[opcode(113)]
+    AcceptOwnership,
+
+    #[opcode(114)]
+    CancelOwnershipProposal,
+
+    #[opcode(115)]
+    GetPendingOwner,
+
+    #[opcode(116)]
+    #[returns(u128)]
+    GetRemainingMintable,
+
+    #[opcode(117)]
+    #[returns(u128)]
+    Burn,
+
+    #[opcode(118)]
+    #[returns(u128)]
+    GetBurned,
+
+    #[opcode(119)]
+    GetRateLimits,
+
+    #[opcode(120)]
+    #[returns(u128)]
+    GetDecimals,
+
+    #[opcode(121)]
+    MintExactSplit,
+
+    #[opcode(122)]
+    #[returns(u128)]
+    GetTotalSupplyAt { height: u128 },
+
+    #[opcode(123)]
+    GetMintSchedule,
+
     #[opcode(1000)]
     #[returns(Vec<u8>)]
     GetData,
@@ -116,15 +403,66 @@ impl ControlledMint {
 
         let mut byte_reader = Cursor::new(get_byte_array_from_inputs(&context.inputs));
 
-        let consts =
+        let mut consts =
             SchemaControlledMintInitializationParameters::deserialize_reader(&mut byte_reader)
                 .map_err(|_| anyhow!("TORTILLA: Failed to decode initialization parameters"))?;
 
+        match &consts.mint_schedule {
+            Some(SchemaMintSchedule::Linear {
+                start_height,
+                end_height,
+                ..
+            }) => ensure!(
+                end_height > start_height,
+                "TORTILLA: Linear mint schedule must end after it starts"
+            ),
+            Some(SchemaMintSchedule::Stepwise { steps }) => ensure!(
+                !steps.is_empty()
+                    && steps
+                        .windows(2)
+                        .all(|w| w[0].height < w[1].height && w[0].amount <= w[1].amount),
+                "TORTILLA: Mint schedule steps must have increasing heights and non-decreasing amounts"
+            ),
+            None => {}
+        }
+
+        if let Some(window) = consts.max_mint_per_window {
+            ensure!(
+                window.blocks > 0 && window.blocks <= MAX_MINT_WINDOW_BLOCKS,
+                "TORTILLA: Mint window must be between 1 and {MAX_MINT_WINDOW_BLOCKS} blocks"
+            );
+        }
+
+        let caller = SchemaAlkaneId {
+            block: context.caller.block.try_into()?,
+            tx: context.caller.tx.try_into()?,
+        };
+
         self.get_owner_pointer()
-            .set(Arc::new(borsh::to_vec(&SchemaAlkaneId {
-                block: context.caller.block.try_into()?,
-                tx: context.caller.tx.try_into()?,
-            })?));
+            .set(Arc::new(borsh::to_vec(&caller)?));
+
+        //A deploying contract starts out as the only minter. Deployed straight from a transaction
+        //there is nobody to trust with it, so that token only ever holds its premine
+        if !caller.is_null() {
+            self.set_minter_list(&[SchemaMinter {
+                alkane_id: caller,
+                allowance: u128::MAX,
+            }])?;
+        }
+
+        //Kept apart from the consts so an inline image isn't decoded on every mint
+        if let Some(metadata) = consts.metadata.take() {
+            self.get_metadata_pointer()
+                .set(Arc::new(borsh::to_vec(&metadata)?));
+        }
+
+        if let Some(splits) = &consts.premine_splits {
+            ensure!(
+                self.get_split_total(&context, splits)? == consts.premine,
+                "TORTILLA: Premine splits must add up to the premine"
+            );
+        }
+
         let consumed_bytes = borsh::to_vec(&consts)?;
 
         self.get_consts_pointer().set(Arc::new(consumed_bytes));
@@ -150,11 +488,30 @@ impl ControlledMint {
 
     pub fn mint_exact(&self, amount: u128) -> Result<CallResponse> {
         let context = self.context()?;
-        self.assert_owner(&context)?;
+        self.mint_as_minter(&context, amount)
+    }
+
+    //MintExact for the splits' total, the split protostone then routes each amount
+    pub fn mint_exact_split(&self) -> Result<CallResponse> {
+        let context = self.context()?;
+
+        let mut byte_reader = Cursor::new(get_byte_array_from_inputs(&context.inputs));
+        let params = SchemaMintExactSplitParameters::deserialize_reader(&mut byte_reader)
+            .map_err(|_| anyhow!("TORTILLA: Failed to decode mint split parameters"))?;
+
+        let amount = self.get_split_total(&context, &params.splits)?;
+
+        self.mint_as_minter(&context, amount)
+    }
+
+    fn mint_as_minter(&self, context: &Context, amount: u128) -> Result<CallResponse> {
+        self.spend_allowance(context, amount)?;
+        self.enforce_rate_limits(amount)?;
+        self.enforce_mint_schedule(amount)?;
 
         let mut response = CallResponse::forward(&context.incoming_alkanes);
 
-        response.alkanes.0.push(self.mint(&context, amount)?);
+        response.alkanes.0.push(self.mint(context, amount)?);
 
         Ok(response)
     }
@@ -173,6 +530,212 @@ impl ControlledMint {
         let null_owner_bytes = borsh::to_vec(&null_owner)?;
 
         self.get_owner_pointer().set(Arc::new(null_owner_bytes));
+        self.get_pending_owner_pointer().set(Arc::new(vec![]));
+
+        //Renouncing stops all minting, nobody is left who could grant a role again
+        self.set_minter_list(&[])?;
+        Ok(response)
+    }
+
+    pub fn get_mint_schedule(&self) -> Result<CallResponse> {
+        let context = self.context()?;
+        let mut response = CallResponse::forward(&context.incoming_alkanes);
+
+        let schedule = self.get_consts().mint_schedule;
+        let unlocked = schedule
+            .as_ref()
+            .map(|schedule| schedule.unlocked_at(self.height()))
+            .unwrap_or(0);
+
+        response.data = borsh::to_vec(&SchemaMintScheduleView {
+            schedule,
+            unlocked,
+            minted: self.get_scheduled_minted_pointer().get_value::<u128>(),
+        })?;
+
+        Ok(response)
+    }
+
+    pub fn get_rate_limits(&self) -> Result<CallResponse> {
+        let context = self.context()?;
+        let mut response = CallResponse::forward(&context.incoming_alkanes);
+
+        let consts = self.get_consts();
+        let (_, minted_this_block, minted_in_window) = self.get_rate_limit_usage(&consts)?;
+
+        response.data = borsh::to_vec(&SchemaMintRateLimitView {
+            max_mint_per_block: consts.max_mint_per_block,
+            max_mint_per_window: consts.max_mint_per_window,
+            minted_this_block,
+            minted_in_window,
+        })?;
+
+        Ok(response)
+    }
+
+    //Anyone can burn, only transfers of this token are consumed and everything else is sent back
+    pub fn burn(&self) -> Result<CallResponse> {
+        let context = self.context()?;
+        let mut response = CallResponse::default();
+
+        let mut burned: u128 = 0;
+
+        for transfer in context.incoming_alkanes.0.iter() {
+            if transfer.id == context.myself {
+                burned = burned
+                    .checked_add(transfer.value)
+                    .ok_or_else(|| anyhow!("TORTILLA: overflow while summing burn amount"))?;
+            } else {
+                response.alkanes.0.push(transfer.clone());
+            }
+        }
+
+        ensure!(burned > 0, "TORTILLA: Nothing to burn");
+
+        self.burn_supply(burned)?;
+
+        response.data = burned.to_le_bytes().to_vec();
+
+        Ok(response)
+    }
+
+    //Nothing changes until the proposed owner accepts, proposing again replaces the pending owner
+    pub fn propose_owner(&self, block: u128, tx: u128) -> Result<CallResponse> {
+        let context = self.context()?;
+        self.assert_owner(&context)?;
+        let response = CallResponse::forward(&context.incoming_alkanes);
+
+        let pending_owner = self.to_schema_alkane_id(block, tx)?;
+        ensure!(
+            !pending_owner.is_null(),
+            "TORTILLA: Ownership can't be proposed to 0:0, use RenounceOwnership"
+        );
+
+        self.get_pending_owner_pointer()
+            .set(Arc::new(borsh::to_vec(&pending_owner)?));
+        Ok(response)
+    }
+
+    pub fn accept_ownership(&self) -> Result<CallResponse> {
+        let context = self.context()?;
+        let response = CallResponse::forward(&context.incoming_alkanes);
+
+        let pending_owner = self
+            .get_pending_owner_id()?
+            .ok_or_else(|| anyhow!("TORTILLA: No ownership transfer is pending"))?;
+
+        ensure!(
+            self.to_schema_alkane_id(context.caller.block, context.caller.tx)? == pending_owner,
+            "TORTILLA: Caller is not the pending owner"
+        );
+
+        let previous_owner = self.get_owner_id()?;
+        self.move_minter_role(previous_owner, pending_owner)?;
+
+        self.get_owner_pointer()
+            .set(Arc::new(borsh::to_vec(&pending_owner)?));
+        self.get_pending_owner_pointer().set(Arc::new(vec![]));
+        Ok(response)
+    }
+
+    pub fn cancel_ownership_proposal(&self) -> Result<CallResponse> {
+        let context = self.context()?;
+        self.assert_owner(&context)?;
+        let response = CallResponse::forward(&context.incoming_alkanes);
+
+        ensure!(
+            self.get_pending_owner_id()?.is_some(),
+            "TORTILLA: No ownership transfer is pending"
+        );
+
+        self.get_pending_owner_pointer().set(Arc::new(vec![]));
+        Ok(response)
+    }
+
+    pub fn get_pending_owner(&self) -> Result<CallResponse> {
+        let context = self.context()?;
+        let mut response = CallResponse::forward(&context.incoming_alkanes);
+
+        //Bytes are of type SchemaAlkaneID, empty when nothing is pending
+        response.data = (*self.get_pending_owner_pointer().get()).clone();
+
+        Ok(response)
+    }
+
+    //Granting an existing minter replaces its allowance
+    pub fn grant_minter(&self, block: u128, tx: u128, allowance: u128) -> Result<CallResponse> {
+        let context = self.context()?;
+        self.assert_owner(&context)?;
+        let response = CallResponse::forward(&context.incoming_alkanes);
+
+        let alkane_id = self.to_schema_alkane_id(block, tx)?;
+        ensure!(
+            !alkane_id.is_null(),
+            "TORTILLA: 0:0 can't be a minter, it is any transaction calling directly"
+        );
+
+        let mut minters = self.get_minter_list()?;
+
+        match minters
+            .iter_mut()
+            .find(|minter| minter.alkane_id == alkane_id)
+        {
+            Some(minter) => minter.allowance = allowance,
+            None => minters.push(SchemaMinter {
+                alkane_id,
+                allowance,
+            }),
+        }
+
+        self.set_minter_list(&minters)?;
+        Ok(response)
+    }
+
+    pub fn revoke_minter(&self, block: u128, tx: u128) -> Result<CallResponse> {
+        let context = self.context()?;
+        self.assert_owner(&context)?;
+        let response = CallResponse::forward(&context.incoming_alkanes);
+
+        let alkane_id = self.to_schema_alkane_id(block, tx)?;
+        let mut minters = self.get_minter_list()?;
+        let minters_before = minters.len();
+
+        minters.retain(|minter| minter.alkane_id != alkane_id);
+
+        ensure!(
+            minters.len() < minters_before,
+            "TORTILLA: Alkane is not a minter"
+        );
+
+        self.set_minter_list(&minters)?;
+        Ok(response)
+    }
+
+    pub fn get_minters(&self) -> Result<CallResponse> {
+        let context = self.context()?;
+        let mut response = CallResponse::forward(&context.incoming_alkanes);
+
+        //Bytes are of type Vec<SchemaMinter>
+        response.data = borsh::to_vec(&self.get_minter_list()?)?;
+
+        Ok(response)
+    }
+
+    //0 for alkanes that aren't minters, u128::MAX for unlimited ones
+    pub fn get_allowance(&self, block: u128, tx: u128) -> Result<CallResponse> {
+        let context = self.context()?;
+        let mut response = CallResponse::forward(&context.incoming_alkanes);
+
+        let alkane_id = self.to_schema_alkane_id(block, tx)?;
+        let allowance = self
+            .get_minter_list()?
+            .into_iter()
+            .find(|minter| minter.alkane_id == alkane_id)
+            .map(|minter| minter.allowance)
+            .unwrap_or(0);
+
+        response.data = allowance.to_le_bytes().to_vec();
+
         Ok(response)
     }
 }
@@ -1,15 +1,142 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 
-#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, Copy)]
 pub struct SchemaAlkaneId {
     pub block: u32,
     pub tx: u64,
 }
 
+impl SchemaAlkaneId {
+    //0:0 is what every top level transaction calls from, so it can never identify an owner
+    pub fn is_null(&self) -> bool {
+        self.block == 0 && self.tx == 0
+    }
+}
+
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
 pub struct SchemaControlledMintInitializationParameters {
     pub token_name: String,
     pub token_symbol: String,
     pub premine: u128,
     pub cap: u128,
+    pub max_mint_per_block: Option<u128>,
+    pub max_mint_per_window: Option<SchemaMintWindow>,
+    //Moved to its own storage on initialize, so it always reads back as None from the stored consts
+    pub metadata: Option<SchemaTokenMetadata>,
+    //Bounds everything minted after initialize, the premine sits outside the schedule
+    pub mint_schedule: Option<SchemaMintSchedule>,
+    //When set the amounts must add up to premine
+    pub premine_splits: Option<Vec<SchemaMintSplit>>,
+}
+
+//Amounts are cumulative, ie: how much may have been minted in total by a given height
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub enum SchemaMintSchedule {
+    //Nothing before start_height, all of amount from end_height, linear in between
+    Linear {
+        start_height: u64,
+        end_height: u64,
+        amount: u128,
+    },
+    //Each step unlocks up to its amount from its height on
+    Stepwise {
+        steps: Vec<SchemaUnlockStep>,
+    },
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct SchemaUnlockStep {
+    pub height: u64,
+    pub amount: u128,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct SchemaMintScheduleView {
+    pub schedule: Option<SchemaMintSchedule>,
+    pub unlocked: u128,
+    pub minted: u128,
+}
+
+impl SchemaMintSchedule {
+    pub fn unlocked_at(&self, height: u64) -> u128 {
+        match self {
+            SchemaMintSchedule::Linear {
+                start_height,
+                end_height,
+                amount,
+            } => {
+                if height <= *start_height {
+                    return 0;
+                }
+                if height >= *end_height {
+                    return *amount;
+                }
+
+                //Split so amount * elapsed can't overflow
+                let span = (*end_height - *start_height) as u128;
+                let elapsed = (height - *start_height) as u128;
+                (*amount / span) * elapsed + (*amount % span) * elapsed / span
+            }
+            SchemaMintSchedule::Stepwise { steps } => steps
+                .iter()
+                .take_while(|step| step.height <= height)
+                .last()
+                .map(|step| step.amount)
+                .unwrap_or(0),
+        }
+    }
+}
+
+//output is a real vout of the transaction, routed there by the split protostone's edicts
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct SchemaMintSplit {
+    pub amount: u128,
+    pub output: u32,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct SchemaMintExactSplitParameters {
+    pub splits: Vec<SchemaMintSplit>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct SchemaTokenMetadata {
+    pub decimals: u8,
+    pub description: Option<String>,
+    pub image: Option<SchemaTokenImage>,
+    pub external_url: Option<String>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub enum SchemaTokenImage {
+    Uri(String),
+    Inline(Vec<u8>),
+}
+
+//At most amount can be minted across any run of blocks consecutive blocks
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, Copy)]
+pub struct SchemaMintWindow {
+    pub blocks: u64,
+    pub amount: u128,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct SchemaMintRecord {
+    pub height: u64,
+    pub amount: u128,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct SchemaMintRateLimitView {
+    pub max_mint_per_block: Option<u128>,
+    pub max_mint_per_window: Option<SchemaMintWindow>,
+    pub minted_this_block: u128,
+    pub minted_in_window: u128,
+}
+
+//allowance is what the minter has left to mint, u128::MAX means unlimited
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct SchemaMinter {
+    pub alkane_id: SchemaAlkaneId,
+    pub allowance: u128,
 }
@@ -0,0 +1,120 @@
+use crate::tests::helpers::{
+    call, call_spending, deploy, forward_inputs, params, return_u128, token_balance, FORWARDER_ID,
+    START_HEIGHT, TOKEN_ID,
+};
+use crate::tests::shared::alkane_balance;
+use alkanes::indexer::index_block;
+use alkanes::precompiled::{alkanes_std_auth_token_build, alkanes_std_owned_token_build};
+use alkanes::tests::helpers as alkane_helpers;
+use alkanes_support::cellpack::Cellpack;
+use alkanes_support::constants::AUTH_TOKEN_FACTORY_ID;
+use alkanes_support::envelope::RawEnvelope;
+use alkanes_support::id::AlkaneId;
+use anyhow::Result;
+use bitcoin::Block;
+use protorune::test_helpers::create_block_with_coinbase_tx;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+//Deployed after the token, so the owned token lands at 2:3 and its auth token at 2:4
+const FOREIGN_ID: AlkaneId = AlkaneId { block: 2, tx: 3 };
+const FOREIGN_AUTH_ID: AlkaneId = AlkaneId { block: 2, tx: 4 };
+
+fn view(opcode: u128, height: u32) -> Result<u128> {
+    return_u128(&call(vec![opcode], height)?)
+}
+
+fn deploy_binary(binary: Vec<u8>, cellpack: Cellpack, height: u32) -> Result<Block> {
+    let mut block = create_block_with_coinbase_tx(height);
+    block
+        .txdata
+        .push(alkane_helpers::create_multiple_cellpack_with_witness(
+            RawEnvelope::from(binary).to_witness(true),
+            vec![cellpack],
+            false,
+        ));
+    index_block(&block, height)?;
+    Ok(block)
+}
+
+//An unrelated alkane to send along with a burn, the block's last output holds all of it
+fn deploy_foreign_token(amount: u128) -> Result<Block> {
+    deploy_binary(
+        alkanes_std_auth_token_build::get_bytes(),
+        Cellpack {
+            target: AlkaneId {
+                block: 3,
+                tx: AUTH_TOKEN_FACTORY_ID,
+            },
+            inputs: vec![100],
+        },
+        START_HEIGHT + 1,
+    )?;
+
+    deploy_binary(
+        alkanes_std_owned_token_build::get_bytes(),
+        Cellpack {
+            target: AlkaneId { block: 1, tx: 0 },
+            inputs: vec![0, 1, amount],
+        },
+        START_HEIGHT + 2,
+    )
+}
+
+#[wasm_bindgen_test]
+fn test_burn_takes_tokens_out_of_supply() -> Result<()> {
+    let deploy_block = deploy(&params(100, 1_000))?;
+    assert_eq!(token_balance(&deploy_block)?, 100);
+
+    let burn = call_spending(&deploy_block, TOKEN_ID, vec![117], START_HEIGHT + 1)?;
+    assert_eq!(return_u128(&burn)?, 100);
+    assert_eq!(token_balance(&burn)?, 0);
+
+    assert_eq!(view(101, START_HEIGHT + 2)?, 0);
+    assert_eq!(view(118, START_HEIGHT + 3)?, 100);
+
+    //Burned tokens still count against the cap
+    assert_eq!(view(116, START_HEIGHT + 4)?, 900);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_burn_returns_foreign_alkanes() -> Result<()> {
+    deploy(&params(0, 1_000))?;
+    let foreign = deploy_foreign_token(50)?;
+    assert_eq!(alkane_balance(&foreign, FOREIGN_ID)?, 50);
+
+    //Mints next to the foreign alkanes so one output holds both
+    let minted = call_spending(
+        &foreign,
+        FORWARDER_ID,
+        forward_inputs(TOKEN_ID, vec![106, 10]),
+        START_HEIGHT + 3,
+    )?;
+    assert_eq!(token_balance(&minted)?, 10);
+    assert_eq!(alkane_balance(&minted, FOREIGN_ID)?, 50);
+
+    let burn = call_spending(&minted, TOKEN_ID, vec![117], START_HEIGHT + 4)?;
+    assert_eq!(return_u128(&burn)?, 10);
+    assert_eq!(token_balance(&burn)?, 0);
+    assert_eq!(alkane_balance(&burn, FOREIGN_ID)?, 50);
+    assert_eq!(alkane_balance(&burn, FOREIGN_AUTH_ID)?, 1);
+
+    assert_eq!(view(101, START_HEIGHT + 5)?, 0);
+    assert_eq!(view(118, START_HEIGHT + 6)?, 10);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_burn_without_own_tokens_is_rejected() -> Result<()> {
+    deploy(&params(0, 1_000))?;
+    let foreign = deploy_foreign_token(50)?;
+
+    //The failed call refunds the foreign alkanes to the same output
+    let burn = call_spending(&foreign, TOKEN_ID, vec![117], START_HEIGHT + 3)?;
+    assert_eq!(alkane_balance(&burn, FOREIGN_ID)?, 50);
+    assert_eq!(view(118, START_HEIGHT + 4)?, 0);
+
+    Ok(())
+}
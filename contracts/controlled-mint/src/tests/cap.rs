@@ -0,0 +1,89 @@
+use crate::schemas::SchemaMinter;
+use crate::tests::helpers::{
+    call, call_as, deploy, deploy_direct, mint, params, token_balance, FORWARDER_ID, START_HEIGHT,
+};
+use crate::tests::shared::return_data;
+use anyhow::Result;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn test_taqueria_clone_cannot_mint_past_premine() -> Result<()> {
+    let deploy_block = deploy(&params(1, 1))?;
+    assert_eq!(token_balance(&deploy_block)?, 1);
+
+    let block = mint(1, START_HEIGHT + 1)?;
+    assert_eq!(token_balance(&block)?, 0);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_taqueria_clone_rejects_every_mint_path() -> Result<()> {
+    deploy(&params(1, 1))?;
+
+    let mint_exact = mint(1, START_HEIGHT + 1)?;
+    assert_eq!(token_balance(&mint_exact)?, 0);
+
+    let mint_tokens = call_as(FORWARDER_ID, vec![77], START_HEIGHT + 2)?;
+    assert_eq!(token_balance(&mint_tokens)?, 0);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_premine_above_cap_fails_initialization() -> Result<()> {
+    let deploy_block = deploy(&params(2, 1))?;
+    assert_eq!(token_balance(&deploy_block)?, 0);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_mint_up_to_cap_then_rejected() -> Result<()> {
+    deploy(&params(1, 10))?;
+
+    let up_to_cap = mint(9, START_HEIGHT + 1)?;
+    assert_eq!(token_balance(&up_to_cap)?, 9);
+
+    let past_cap = mint(1, START_HEIGHT + 2)?;
+    assert_eq!(token_balance(&past_cap)?, 0);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_null_caller_cannot_mint() -> Result<()> {
+    deploy(&params(1, 10))?;
+
+    let mint_exact = call(vec![106, 1], START_HEIGHT + 1)?;
+    assert_eq!(token_balance(&mint_exact)?, 0);
+
+    let mint_tokens = call(vec![77], START_HEIGHT + 2)?;
+    assert_eq!(token_balance(&mint_tokens)?, 0);
+
+    //0:0 holds no owner authority, and the owner can't hand it the role either
+    call(vec![108, 0, 0, u128::MAX], START_HEIGHT + 3)?;
+    call_as(FORWARDER_ID, vec![108, 0, 0, u128::MAX], START_HEIGHT + 4)?;
+
+    let after_grant = call(vec![106, 1], START_HEIGHT + 5)?;
+    assert_eq!(token_balance(&after_grant)?, 0);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_token_deployed_from_a_transaction_has_no_minters() -> Result<()> {
+    let deploy_block = deploy_direct(&params(1, 10))?;
+    assert_eq!(token_balance(&deploy_block)?, 1);
+
+    let minters = call(vec![110], START_HEIGHT + 1)?;
+    assert_eq!(
+        return_data(&minters)?,
+        borsh::to_vec(&Vec::<SchemaMinter>::new())?
+    );
+
+    let block = call(vec![106, 1], START_HEIGHT + 2)?;
+    assert_eq!(token_balance(&block)?, 0);
+
+    Ok(())
+}
@@ -0,0 +1,221 @@
+use crate::schemas::SchemaControlledMintInitializationParameters;
+use crate::tests::shared::{alkane_balance, return_data, to_inputs};
+use crate::tests::std::{controlled_mint_build, test_forwarder_build};
+use alkanes::indexer::index_block;
+use alkanes::tests::helpers as alkane_helpers;
+use alkanes_support::cellpack::Cellpack;
+use alkanes_support::id::AlkaneId;
+use anyhow::{anyhow, Result};
+use bitcoin::{Block, OutPoint, Witness};
+use protorune::test_helpers::create_block_with_coinbase_tx;
+
+pub const START_HEIGHT: u32 = 840_000;
+
+//The forwarder owns the token and holds its minter role, 0:0 is any transaction calling directly
+pub const FORWARDER_ID: AlkaneId = AlkaneId { block: 2, tx: 0 };
+pub const FACTORY_ID: AlkaneId = AlkaneId { block: 2, tx: 1 };
+pub const TOKEN_ID: AlkaneId = AlkaneId { block: 2, tx: 2 };
+
+pub fn token_balance(block: &Block) -> Result<u128> {
+    alkane_balance(block, TOKEN_ID)
+}
+
+pub fn params(premine: u128, cap: u128) -> SchemaControlledMintInitializationParameters {
+    SchemaControlledMintInitializationParameters {
+        token_name: "TAQUERIA".to_string(),
+        token_symbol: "TAQUERIA".to_string(),
+        premine,
+        cap,
+        max_mint_per_block: None,
+        max_mint_per_window: None,
+        metadata: None,
+        mint_schedule: None,
+        premine_splits: None,
+    }
+}
+
+pub fn forward_inputs(target: AlkaneId, inputs: Vec<u128>) -> Vec<u128> {
+    let mut forwarded = vec![1, target.block, target.tx];
+    forwarded.extend(inputs);
+    forwarded
+}
+
+//Deploys the forwarder at 2:0 and the uninitialized factory at 2:1, then creates the token at 2:2
+//when given one
+fn deploy_with(token: Option<(Vec<u8>, Cellpack)>) -> Result<Block> {
+    alkane_helpers::clear();
+
+    let mut binaries = vec![
+        test_forwarder_build::get_bytes(),
+        controlled_mint_build::get_bytes(),
+    ];
+    let mut cellpacks = vec![
+        Cellpack {
+            target: AlkaneId { block: 1, tx: 0 },
+            inputs: vec![0],
+        },
+        Cellpack {
+            target: AlkaneId { block: 1, tx: 0 },
+            inputs: vec![100],
+        },
+    ];
+
+    if let Some((binary, cellpack)) = token {
+        binaries.push(binary);
+        cellpacks.push(cellpack);
+    }
+
+    let block = alkane_helpers::init_with_multiple_cellpacks_with_tx(binaries, cellpacks);
+    index_block(&block, START_HEIGHT)?;
+
+    Ok(block)
+}
+
+//Only the forwarder and the factory, for tests that create the token in a transaction of their own
+pub fn deploy_fixtures() -> Result<()> {
+    deploy_with(None)?;
+    Ok(())
+}
+
+//Forwarder inputs that clone the factory and initialize the clone with params
+pub fn clone_inputs(params: &SchemaControlledMintInitializationParameters) -> Result<Vec<u128>> {
+    Ok(forward_inputs(
+        AlkaneId {
+            block: 5,
+            tx: FACTORY_ID.tx,
+        },
+        to_inputs(0, &borsh::to_vec(params)?),
+    ))
+}
+
+//Same shape as production: the token is a clone of the factory, initialized by the contract that
+//cloned it (here the forwarder, the game in tacoclicker)
+pub fn deploy(params: &SchemaControlledMintInitializationParameters) -> Result<Block> {
+    deploy_with(Some((
+        vec![],
+        Cellpack {
+            target: FORWARDER_ID,
+            inputs: clone_inputs(params)?,
+        },
+    )))
+}
+
+//The token is deployed and initialized straight from a transaction, so its caller is 0:0
+pub fn deploy_direct(params: &SchemaControlledMintInitializationParameters) -> Result<Block> {
+    deploy_with(Some((
+        controlled_mint_build::get_bytes(),
+        Cellpack {
+            target: AlkaneId { block: 1, tx: 0 },
+            inputs: to_inputs(0, &borsh::to_vec(params)?),
+        },
+    )))
+}
+
+//Clones another forwarder to act as a second contract caller. It lands at 2:sequence, right after
+//deploy that is 2:3
+pub fn deploy_forwarder(sequence: u128, height: u32) -> Result<AlkaneId> {
+    index_cellpacks(
+        vec![Cellpack {
+            target: AlkaneId {
+                block: 5,
+                tx: FORWARDER_ID.tx,
+            },
+            inputs: vec![0],
+        }],
+        height,
+    )?;
+
+    Ok(AlkaneId {
+        block: 2,
+        tx: sequence,
+    })
+}
+
+//Every cellpack becomes its own protostone, so they all run in the same block
+fn index_cellpacks(cellpacks: Vec<Cellpack>, height: u32) -> Result<Block> {
+    let mut block = create_block_with_coinbase_tx(height);
+    block
+        .txdata
+        .push(alkane_helpers::create_multiple_cellpack_with_witness(
+            Witness::new(),
+            cellpacks,
+            false,
+        ));
+    index_block(&block, height)?;
+    Ok(block)
+}
+
+pub fn call_many(calls: Vec<Vec<u128>>, height: u32) -> Result<Block> {
+    index_cellpacks(
+        calls
+            .into_iter()
+            .map(|inputs| Cellpack {
+                target: TOKEN_ID,
+                inputs,
+            })
+            .collect(),
+        height,
+    )
+}
+
+pub fn call(inputs: Vec<u128>, height: u32) -> Result<Block> {
+    call_many(vec![inputs], height)
+}
+
+//Calls the token with caller as the calling contract, caller has to be a forwarder
+pub fn call_as_many(caller: AlkaneId, calls: Vec<Vec<u128>>, height: u32) -> Result<Block> {
+    index_cellpacks(
+        calls
+            .into_iter()
+            .map(|inputs| Cellpack {
+                target: caller,
+                inputs: forward_inputs(TOKEN_ID, inputs),
+            })
+            .collect(),
+        height,
+    )
+}
+
+pub fn call_as(caller: AlkaneId, inputs: Vec<u128>, height: u32) -> Result<Block> {
+    call_as_many(caller, vec![inputs], height)
+}
+
+//Spends vout #0 of the block's last transaction, so the alkanes it holds come in with the call
+pub fn call_spending(
+    spent: &Block,
+    target: AlkaneId,
+    inputs: Vec<u128>,
+    height: u32,
+) -> Result<Block> {
+    let mut block = create_block_with_coinbase_tx(height);
+    block.txdata.push(
+        alkane_helpers::create_multiple_cellpack_with_witness_and_in(
+            Witness::new(),
+            vec![Cellpack { target, inputs }],
+            OutPoint {
+                txid: spent.txdata.last().unwrap().compute_txid(),
+                vout: 0,
+            },
+            false,
+        ),
+    );
+    index_block(&block, height)?;
+    Ok(block)
+}
+
+pub fn mint(amount: u128, height: u32) -> Result<Block> {
+    call_as(FORWARDER_ID, vec![106, amount], height)
+}
+
+pub fn allowance_of(minter: AlkaneId, height: u32) -> Result<u128> {
+    return_u128(&call(vec![111, minter.block, minter.tx], height)?)
+}
+
+pub fn return_u128(block: &Block) -> Result<u128> {
+    let data = return_data(block)?;
+    let bytes: [u8; 16] = data
+        .as_slice()
+        .try_into()
+        .map_err(|_| anyhow!("expected 16 bytes, got {}", data.len()))?;
+    Ok(u128::from_le_bytes(bytes))
+}
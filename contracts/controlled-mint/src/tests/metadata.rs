@@ -0,0 +1,108 @@
+use crate::schemas::{SchemaTokenImage, SchemaTokenMetadata};
+use crate::tests::helpers::{call, deploy, params, return_u128, START_HEIGHT};
+use crate::tests::shared::return_data;
+use crate::token::DEFAULT_DECIMALS;
+use anyhow::Result;
+use serde_json::{json, Value};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn metadata(image: Option<SchemaTokenImage>) -> SchemaTokenMetadata {
+    SchemaTokenMetadata {
+        decimals: 0,
+        description: Some("A Taco Clicker taqueria".to_string()),
+        image,
+        external_url: Some("https://tacoclicker.com".to_string()),
+    }
+}
+
+fn get_data(height: u32) -> Result<Vec<u8>> {
+    return_data(&call(vec![1000], height)?)
+}
+
+#[wasm_bindgen_test]
+fn test_decimals_come_from_metadata() -> Result<()> {
+    let mut token = params(1, 1);
+    token.metadata = Some(metadata(None));
+    deploy(&token)?;
+
+    assert_eq!(return_u128(&call(vec![120], START_HEIGHT + 1)?)?, 0);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_decimals_default_without_metadata() -> Result<()> {
+    deploy(&params(1, 1))?;
+
+    assert_eq!(
+        return_u128(&call(vec![120], START_HEIGHT + 1)?)?,
+        DEFAULT_DECIMALS as u128
+    );
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_get_data_describes_the_token_as_json() -> Result<()> {
+    let mut token = params(1, 1);
+    token.metadata = Some(metadata(Some(SchemaTokenImage::Uri(
+        "ipfs://taqueria.png".to_string(),
+    ))));
+    deploy(&token)?;
+
+    let document: Value = serde_json::from_slice(&get_data(START_HEIGHT + 1)?)?;
+    assert_eq!(
+        document,
+        json!({
+            "name": "TAQUERIA",
+            "symbol": "TAQUERIA",
+            "decimals": 0,
+            "description": "A Taco Clicker taqueria",
+            "image": "ipfs://taqueria.png",
+            "external_url": "https://tacoclicker.com",
+        })
+    );
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_get_data_without_metadata_leaves_fields_empty() -> Result<()> {
+    deploy(&params(1, 1))?;
+
+    let document: Value = serde_json::from_slice(&get_data(START_HEIGHT + 1)?)?;
+    assert_eq!(
+        document,
+        json!({
+            "name": "TAQUERIA",
+            "symbol": "TAQUERIA",
+            "decimals": DEFAULT_DECIMALS,
+            "description": null,
+            "image": null,
+            "external_url": null,
+        })
+    );
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_get_data_returns_inline_image_bytes() -> Result<()> {
+    //PNG signature, enough to tell the bytes came back untouched
+    let image = vec![0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+
+    let mut token = params(1, 1);
+    token.metadata = Some(metadata(Some(SchemaTokenImage::Inline(image.clone()))));
+    deploy(&token)?;
+
+    assert_eq!(get_data(START_HEIGHT + 1)?, image);
+
+    //The rest of the metadata is still served by the getters
+    assert_eq!(return_u128(&call(vec![120], START_HEIGHT + 2)?)?, 0);
+    assert_eq!(
+        return_data(&call(vec![99], START_HEIGHT + 3)?)?,
+        b"TAQUERIA".to_vec()
+    );
+
+    Ok(())
+}
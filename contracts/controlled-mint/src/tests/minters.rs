@@ -0,0 +1,130 @@
+use crate::tests::helpers::{
+    allowance_of, call_as, deploy, deploy_forwarder, mint, params, token_balance, FORWARDER_ID,
+    START_HEIGHT,
+};
+use alkanes_support::id::AlkaneId;
+use anyhow::Result;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+//Token deployed with a second forwarder at 2:3 that holds no role yet
+fn deploy_with_minter() -> Result<AlkaneId> {
+    deploy(&params(0, 1_000))?;
+    deploy_forwarder(3, START_HEIGHT + 1)
+}
+
+#[wasm_bindgen_test]
+fn test_initializing_contract_is_an_unlimited_minter() -> Result<()> {
+    deploy(&params(0, 1_000))?;
+
+    let block = mint(400, START_HEIGHT + 1)?;
+    assert_eq!(token_balance(&block)?, 400);
+
+    //Unlimited allowances aren't spent
+    assert_eq!(allowance_of(FORWARDER_ID, START_HEIGHT + 2)?, u128::MAX);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_granted_allowance_decrements_until_exhausted() -> Result<()> {
+    let minter = deploy_with_minter()?;
+
+    let before_grant = call_as(minter, vec![106, 1], START_HEIGHT + 2)?;
+    assert_eq!(token_balance(&before_grant)?, 0);
+
+    call_as(
+        FORWARDER_ID,
+        vec![108, minter.block, minter.tx, 10],
+        START_HEIGHT + 3,
+    )?;
+    assert_eq!(allowance_of(minter, START_HEIGHT + 4)?, 10);
+
+    let first = call_as(minter, vec![106, 4], START_HEIGHT + 5)?;
+    assert_eq!(token_balance(&first)?, 4);
+    assert_eq!(allowance_of(minter, START_HEIGHT + 6)?, 6);
+
+    //Going over the allowance mints nothing and leaves it untouched
+    let over = call_as(minter, vec![106, 7], START_HEIGHT + 7)?;
+    assert_eq!(token_balance(&over)?, 0);
+    assert_eq!(allowance_of(minter, START_HEIGHT + 8)?, 6);
+
+    let rest = call_as(minter, vec![106, 6], START_HEIGHT + 9)?;
+    assert_eq!(token_balance(&rest)?, 6);
+    assert_eq!(allowance_of(minter, START_HEIGHT + 10)?, 0);
+
+    let exhausted = call_as(minter, vec![106, 1], START_HEIGHT + 11)?;
+    assert_eq!(token_balance(&exhausted)?, 0);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_granting_again_replaces_the_allowance() -> Result<()> {
+    let minter = deploy_with_minter()?;
+
+    call_as(
+        FORWARDER_ID,
+        vec![108, minter.block, minter.tx, 10],
+        START_HEIGHT + 2,
+    )?;
+    call_as(
+        FORWARDER_ID,
+        vec![108, minter.block, minter.tx, 3],
+        START_HEIGHT + 3,
+    )?;
+    assert_eq!(allowance_of(minter, START_HEIGHT + 4)?, 3);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_minter_cannot_grant_itself_more() -> Result<()> {
+    let minter = deploy_with_minter()?;
+
+    call_as(
+        FORWARDER_ID,
+        vec![108, minter.block, minter.tx, 10],
+        START_HEIGHT + 2,
+    )?;
+    call_as(
+        minter,
+        vec![108, minter.block, minter.tx, u128::MAX],
+        START_HEIGHT + 3,
+    )?;
+    assert_eq!(allowance_of(minter, START_HEIGHT + 4)?, 10);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_revoked_minter_cannot_mint() -> Result<()> {
+    let minter = deploy_with_minter()?;
+
+    call_as(
+        FORWARDER_ID,
+        vec![108, minter.block, minter.tx, 10],
+        START_HEIGHT + 2,
+    )?;
+    let before_revoke = call_as(minter, vec![106, 1], START_HEIGHT + 3)?;
+    assert_eq!(token_balance(&before_revoke)?, 1);
+
+    //Only the owner can revoke
+    call_as(minter, vec![109, minter.block, minter.tx], START_HEIGHT + 4)?;
+    assert_eq!(allowance_of(minter, START_HEIGHT + 5)?, 9);
+
+    call_as(
+        FORWARDER_ID,
+        vec![109, minter.block, minter.tx],
+        START_HEIGHT + 6,
+    )?;
+    assert_eq!(allowance_of(minter, START_HEIGHT + 7)?, 0);
+
+    let after_revoke = call_as(minter, vec![106, 1], START_HEIGHT + 8)?;
+    assert_eq!(token_balance(&after_revoke)?, 0);
+
+    //The owner's own role is untouched
+    let owner_mint = mint(1, START_HEIGHT + 9)?;
+    assert_eq!(token_balance(&owner_mint)?, 1);
+
+    Ok(())
+}
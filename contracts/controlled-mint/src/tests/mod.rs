@@ -0,0 +1,14 @@
+pub mod std;
+
+mod burn;
+mod cap;
+mod helpers;
+mod metadata;
+mod minters;
+mod ownership;
+mod rate_limits;
+mod schedule;
+#[path = "../../../build-support/test_helpers.rs"]
+mod shared;
+mod splits;
+mod supply;
@@ -0,0 +1,156 @@
+use crate::schemas::SchemaAlkaneId;
+use crate::tests::helpers::{
+    allowance_of, call, call_as, deploy, deploy_forwarder, mint, params, token_balance,
+    FORWARDER_ID, START_HEIGHT,
+};
+use crate::tests::shared::return_data;
+use alkanes_support::id::AlkaneId;
+use anyhow::Result;
+use borsh::BorshDeserialize;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn owner(height: u32) -> Result<SchemaAlkaneId> {
+    let data = return_data(&call(vec![105], height)?)?;
+    Ok(SchemaAlkaneId::try_from_slice(&data)?)
+}
+
+fn pending_owner(height: u32) -> Result<Option<SchemaAlkaneId>> {
+    let data = return_data(&call(vec![115], height)?)?;
+    if data.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(SchemaAlkaneId::try_from_slice(&data)?))
+}
+
+fn schema_id(id: AlkaneId) -> SchemaAlkaneId {
+    SchemaAlkaneId {
+        block: id.block as u32,
+        tx: id.tx as u64,
+    }
+}
+
+//Token owned by the forwarder at 2:0, with a second forwarder at 2:3 to hand it to
+fn deploy_with_successor() -> Result<AlkaneId> {
+    deploy(&params(0, 1_000))?;
+    deploy_forwarder(3, START_HEIGHT + 1)
+}
+
+#[wasm_bindgen_test]
+fn test_accepted_transfer_moves_owner_and_minter_role() -> Result<()> {
+    let successor = deploy_with_successor()?;
+
+    call_as(
+        FORWARDER_ID,
+        vec![112, successor.block, successor.tx],
+        START_HEIGHT + 2,
+    )?;
+
+    //Proposing changes nothing yet
+    assert_eq!(pending_owner(START_HEIGHT + 3)?, Some(schema_id(successor)));
+    assert_eq!(owner(START_HEIGHT + 4)?, schema_id(FORWARDER_ID));
+    let before_accept = call_as(successor, vec![106, 1], START_HEIGHT + 5)?;
+    assert_eq!(token_balance(&before_accept)?, 0);
+
+    call_as(successor, vec![113], START_HEIGHT + 6)?;
+
+    assert_eq!(owner(START_HEIGHT + 7)?, schema_id(successor));
+    assert_eq!(pending_owner(START_HEIGHT + 8)?, None);
+    assert_eq!(allowance_of(FORWARDER_ID, START_HEIGHT + 9)?, 0);
+    assert_eq!(allowance_of(successor, START_HEIGHT + 10)?, u128::MAX);
+
+    let old_owner_mint = mint(1, START_HEIGHT + 11)?;
+    assert_eq!(token_balance(&old_owner_mint)?, 0);
+
+    let new_owner_mint = call_as(successor, vec![106, 1], START_HEIGHT + 12)?;
+    assert_eq!(token_balance(&new_owner_mint)?, 1);
+
+    //Owner opcodes follow the role
+    call_as(FORWARDER_ID, vec![108, 2, 0, 5], START_HEIGHT + 13)?;
+    assert_eq!(allowance_of(FORWARDER_ID, START_HEIGHT + 14)?, 0);
+
+    call_as(successor, vec![108, 2, 0, 5], START_HEIGHT + 15)?;
+    assert_eq!(allowance_of(FORWARDER_ID, START_HEIGHT + 16)?, 5);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_only_the_pending_owner_can_accept() -> Result<()> {
+    let successor = deploy_with_successor()?;
+
+    //Nothing pending yet
+    call_as(successor, vec![113], START_HEIGHT + 2)?;
+    assert_eq!(owner(START_HEIGHT + 3)?, schema_id(FORWARDER_ID));
+
+    call_as(
+        FORWARDER_ID,
+        vec![112, successor.block, successor.tx],
+        START_HEIGHT + 4,
+    )?;
+
+    //Neither the current owner nor a plain transaction can accept on its behalf
+    call_as(FORWARDER_ID, vec![113], START_HEIGHT + 5)?;
+    call(vec![113], START_HEIGHT + 6)?;
+    assert_eq!(owner(START_HEIGHT + 7)?, schema_id(FORWARDER_ID));
+    assert_eq!(pending_owner(START_HEIGHT + 8)?, Some(schema_id(successor)));
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_cancelled_proposal_cannot_be_accepted() -> Result<()> {
+    let successor = deploy_with_successor()?;
+
+    call_as(
+        FORWARDER_ID,
+        vec![112, successor.block, successor.tx],
+        START_HEIGHT + 2,
+    )?;
+
+    //Only the owner can cancel
+    call_as(successor, vec![114], START_HEIGHT + 3)?;
+    assert_eq!(pending_owner(START_HEIGHT + 4)?, Some(schema_id(successor)));
+
+    call_as(FORWARDER_ID, vec![114], START_HEIGHT + 5)?;
+    assert_eq!(pending_owner(START_HEIGHT + 6)?, None);
+
+    call_as(successor, vec![113], START_HEIGHT + 7)?;
+    assert_eq!(owner(START_HEIGHT + 8)?, schema_id(FORWARDER_ID));
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_transfer_adds_allowance_to_an_existing_minter() -> Result<()> {
+    let successor = deploy_with_successor()?;
+
+    //The owner caps itself at 7 and the successor already holds 5
+    call_as(FORWARDER_ID, vec![108, 2, 0, 7], START_HEIGHT + 2)?;
+    call_as(
+        FORWARDER_ID,
+        vec![108, successor.block, successor.tx, 5],
+        START_HEIGHT + 3,
+    )?;
+
+    call_as(
+        FORWARDER_ID,
+        vec![112, successor.block, successor.tx],
+        START_HEIGHT + 4,
+    )?;
+    call_as(successor, vec![113], START_HEIGHT + 5)?;
+
+    assert_eq!(allowance_of(successor, START_HEIGHT + 6)?, 12);
+    assert_eq!(allowance_of(FORWARDER_ID, START_HEIGHT + 7)?, 0);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_owner_cannot_be_proposed_as_null() -> Result<()> {
+    deploy(&params(0, 1_000))?;
+
+    call_as(FORWARDER_ID, vec![112, 0, 0], START_HEIGHT + 1)?;
+    assert_eq!(pending_owner(START_HEIGHT + 2)?, None);
+
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use crate::schemas::{SchemaMintRateLimitView, SchemaMintWindow};
+use crate::tests::helpers::{
+    call, call_as_many, deploy, mint, params, return_u128, token_balance, FORWARDER_ID,
+    START_HEIGHT,
+};
+use crate::tests::shared::return_data;
+use anyhow::Result;
+use borsh::BorshDeserialize;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn total_supply(height: u32) -> Result<u128> {
+    return_u128(&call(vec![101], height)?)
+}
+
+fn rate_limits(height: u32) -> Result<SchemaMintRateLimitView> {
+    let data = return_data(&call(vec![119], height)?)?;
+    Ok(SchemaMintRateLimitView::try_from_slice(&data)?)
+}
+
+fn window(blocks: u64, amount: u128) -> SchemaMintWindow {
+    SchemaMintWindow { blocks, amount }
+}
+
+#[wasm_bindgen_test]
+fn test_per_block_limit_rejects_the_mint_that_crosses_it() -> Result<()> {
+    let mut token = params(0, 1_000);
+    token.max_mint_per_block = Some(10);
+    deploy(&token)?;
+
+    let over = mint(11, START_HEIGHT + 1)?;
+    assert_eq!(token_balance(&over)?, 0);
+
+    //Both mints run in one block, the second would take it to 11
+    call_as_many(
+        FORWARDER_ID,
+        vec![vec![106, 6], vec![106, 5]],
+        START_HEIGHT + 2,
+    )?;
+    assert_eq!(total_supply(START_HEIGHT + 3)?, 6);
+
+    //Each block starts over
+    let next_block = mint(10, START_HEIGHT + 4)?;
+    assert_eq!(token_balance(&next_block)?, 10);
+
+    let view = rate_limits(START_HEIGHT + 5)?;
+    assert_eq!(view.max_mint_per_block, Some(10));
+    assert_eq!(view.minted_this_block, 0);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_window_limit_rolls_over() -> Result<()> {
+    let mut token = params(0, 1_000);
+    token.max_mint_per_window = Some(window(10, 20));
+    deploy(&token)?;
+
+    let first = mint(15, START_HEIGHT + 1)?;
+    assert_eq!(token_balance(&first)?, 15);
+
+    //A 10 block window at START_HEIGHT + 10 still starts at START_HEIGHT + 1
+    let over = mint(6, START_HEIGHT + 9)?;
+    assert_eq!(token_balance(&over)?, 0);
+
+    let fill = mint(5, START_HEIGHT + 10)?;
+    assert_eq!(token_balance(&fill)?, 5);
+
+    //One block later the first mint falls out of the window, the second one stays in
+    let view = rate_limits(START_HEIGHT + 11)?;
+    assert_eq!(view.minted_in_window, 5);
+
+    let after_rollover = mint(15, START_HEIGHT + 11)?;
+    assert_eq!(token_balance(&after_rollover)?, 15);
+
+    let full = mint(1, START_HEIGHT + 12)?;
+    assert_eq!(token_balance(&full)?, 0);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_window_length_is_bounded() -> Result<()> {
+    for (blocks, expected_premine) in [(0, 0), (4_033, 0), (u64::MAX, 0), (1, 1), (4_032, 1)] {
+        let mut token = params(1, 1_000);
+        token.max_mint_per_window = Some(window(blocks, 100));
+
+        //A rejected window fails initialize, so the premine never shows up
+        let deploy_block = deploy(&token)?;
+        assert_eq!(
+            token_balance(&deploy_block)?,
+            premine,
+            "{blocks} block window"
+        );
+    }
+
+    Ok(())
+}
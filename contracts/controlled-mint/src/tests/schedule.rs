@@ -0,0 +1,133 @@
+use crate::schemas::{SchemaMintSchedule, SchemaUnlockStep};
+use crate::tests::helpers::{deploy, mint, params, token_balance, START_HEIGHT};
+use anyhow::Result;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn linear(start_height: u64, end_height: u64, amount: u128) -> SchemaMintSchedule {
+    SchemaMintSchedule::Linear {
+        start_height,
+        end_height,
+        amount,
+    }
+}
+
+//Cliff at 100, more at 150, everything from 200
+fn stepwise() -> SchemaMintSchedule {
+    SchemaMintSchedule::Stepwise {
+        steps: vec![
+            SchemaUnlockStep {
+                height: 100,
+                amount: 10,
+            },
+            SchemaUnlockStep {
+                height: 150,
+                amount: 40,
+            },
+            SchemaUnlockStep {
+                height: 200,
+                amount: 100,
+            },
+        ],
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_linear_unlocked_at() {
+    let schedule = linear(100, 200, 1_000);
+
+    assert_eq!(schedule.unlocked_at(0), 0);
+    assert_eq!(schedule.unlocked_at(99), 0);
+    assert_eq!(schedule.unlocked_at(100), 0);
+    assert_eq!(schedule.unlocked_at(101), 10);
+    assert_eq!(schedule.unlocked_at(150), 500);
+    assert_eq!(schedule.unlocked_at(199), 990);
+    assert_eq!(schedule.unlocked_at(200), 1_000);
+    assert_eq!(schedule.unlocked_at(u64::MAX), 1_000);
+}
+
+#[wasm_bindgen_test]
+fn test_linear_unlocked_at_does_not_overflow() {
+    let schedule = linear(0, 3, u128::MAX);
+
+    assert_eq!(schedule.unlocked_at(1), u128::MAX / 3);
+    assert_eq!(schedule.unlocked_at(2), u128::MAX / 3 * 2);
+    assert_eq!(schedule.unlocked_at(3), u128::MAX);
+}
+
+#[wasm_bindgen_test]
+fn test_stepwise_unlocked_at() {
+    let schedule = stepwise();
+
+    assert_eq!(schedule.unlocked_at(99), 0);
+    assert_eq!(schedule.unlocked_at(100), 10);
+    //Between steps the previous one holds
+    assert_eq!(schedule.unlocked_at(149), 10);
+    assert_eq!(schedule.unlocked_at(150), 40);
+    assert_eq!(schedule.unlocked_at(200), 100);
+    assert_eq!(schedule.unlocked_at(u64::MAX), 100);
+}
+
+#[wasm_bindgen_test]
+fn test_linear_schedule_bounds_mints() -> Result<()> {
+    let start = START_HEIGHT as u64 + 10;
+    let mut token = params(0, 10_000);
+    token.mint_schedule = Some(linear(start, start + 100, 1_000));
+    deploy(&token)?;
+
+    let before_start = mint(1, START_HEIGHT + 5)?;
+    assert_eq!(token_balance(&before_start)?, 0);
+
+    let at_start = mint(1, START_HEIGHT + 10)?;
+    assert_eq!(token_balance(&at_start)?, 0);
+
+    //500 unlocked halfway, one unit over is rejected
+    let over_by_one = mint(501, START_HEIGHT + 60)?;
+    assert_eq!(token_balance(&over_by_one)?, 0);
+
+    let exact = mint(500, START_HEIGHT + 61)?;
+    assert_eq!(token_balance(&exact)?, 500);
+
+    //Past the end the rest unlocks, and nothing more
+    let rest = mint(500, START_HEIGHT + 500)?;
+    assert_eq!(token_balance(&rest)?, 500);
+
+    let past_end = mint(1, START_HEIGHT + 501)?;
+    assert_eq!(token_balance(&past_end)?, 0);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_stepwise_schedule_bounds_mints() -> Result<()> {
+    let mut token = params(0, 10_000);
+    token.mint_schedule = Some(SchemaMintSchedule::Stepwise {
+        steps: vec![
+            SchemaUnlockStep {
+                height: START_HEIGHT as u64 + 10,
+                amount: 10,
+            },
+            SchemaUnlockStep {
+                height: START_HEIGHT as u64 + 20,
+                amount: 100,
+            },
+        ],
+    });
+    deploy(&token)?;
+
+    let before_cliff = mint(1, START_HEIGHT + 9)?;
+    assert_eq!(token_balance(&before_cliff)?, 0);
+
+    let cliff = mint(10, START_HEIGHT + 10)?;
+    assert_eq!(token_balance(&cliff)?, 10);
+
+    let mid_cliff = mint(1, START_HEIGHT + 15)?;
+    assert_eq!(token_balance(&mid_cliff)?, 0);
+
+    let over_last_step = mint(91, START_HEIGHT + 20)?;
+    assert_eq!(token_balance(&over_last_step)?, 0);
+
+    let last_step = mint(90, START_HEIGHT + 21)?;
+    assert_eq!(token_balance(&last_step)?, 90);
+
+    Ok(())
+}
@@ -0,0 +1,200 @@
+use crate::schemas::{SchemaMintExactSplitParameters, SchemaMintSplit};
+use crate::tests::helpers::{
+    call, clone_inputs, deploy, deploy_fixtures, forward_inputs, params, return_u128, FORWARDER_ID,
+    START_HEIGHT, TOKEN_ID,
+};
+use crate::tests::shared::{
+    address_output, alkane_balance_at, build_tx, index_tx, message, split, to_inputs,
+};
+use alkanes_support::cellpack::Cellpack;
+use anyhow::Result;
+use bitcoin::{Block, OutPoint, Witness};
+use protorune::test_helpers::{get_address, ADDRESS1};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+//BIP-173 regtest vector, anything that isn't ADDRESS1 works
+const OTHER_ADDRESS: &str = "bcrt1qw508d6qejxtdg4y5r3zarvary0c5xw7kygt080";
+
+fn splits(amounts: &[(u128, u32)]) -> Vec<SchemaMintSplit> {
+    amounts
+        .iter()
+        .map(|(amount, output)| SchemaMintSplit {
+            amount: *amount,
+            output: *output,
+        })
+        .collect()
+}
+
+//Outputs are ADDRESS1 then OTHER_ADDRESS. The forwarder's call points at protostone #1, whose
+//edicts move the token to the outputs
+fn call_split(forwarder_inputs: Vec<u128>, edicts: &[(u128, u32)], height: u32) -> Result<Block> {
+    let outputs = vec![
+        address_output(&get_address(&ADDRESS1()).to_string())?,
+        address_output(OTHER_ADDRESS)?,
+    ];
+    //Past the outputs and the OP_RETURN, protostone #1 is shadow vout outputs + 2
+    let split_vout = outputs.len() as u32 + 2;
+
+    let edicts: Vec<_> = edicts
+        .iter()
+        .map(|(amount, output)| (TOKEN_ID, *amount, *output))
+        .collect();
+
+    index_tx(
+        build_tx(
+            vec![OutPoint::null()],
+            Witness::new(),
+            outputs,
+            vec![
+                message(
+                    Cellpack {
+                        target: FORWARDER_ID,
+                        inputs: forwarder_inputs,
+                    },
+                    split_vout,
+                ),
+                split(&edicts),
+            ],
+        )?,
+        height,
+    )
+}
+
+fn deploy_split(premine_splits: &[(u128, u32)], edicts: &[(u128, u32)]) -> Result<Block> {
+    deploy_fixtures()?;
+
+    let mut params = params(1_000, 10_000);
+    params.premine_splits = Some(splits(premine_splits));
+
+    call_split(clone_inputs(&params)?, edicts, START_HEIGHT + 1)
+}
+
+fn mint_split(amounts: &[(u128, u32)], edicts: &[(u128, u32)], height: u32) -> Result<Block> {
+    let params = SchemaMintExactSplitParameters {
+        splits: splits(amounts),
+    };
+    call_split(
+        forward_inputs(TOKEN_ID, to_inputs(121, &borsh::to_vec(&params)?)),
+        edicts,
+        height,
+    )
+}
+
+fn balances(block: &Block) -> Result<(u128, u128)> {
+    Ok((
+        alkane_balance_at(block, 0, TOKEN_ID)?,
+        alkane_balance_at(block, 1, TOKEN_ID)?,
+    ))
+}
+
+#[wasm_bindgen_test]
+fn test_premine_split_pays_each_output() -> Result<()> {
+    let block = deploy_split(&[(600, 0), (400, 1)], &[(600, 0), (400, 1)])?;
+    assert_eq!(balances(&block)?, (600, 400));
+
+    let supply = return_u128(&call(vec![101], START_HEIGHT + 2)?)?;
+    assert_eq!(supply, 1_000);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_premine_split_not_matching_the_edicts_is_rejected() -> Result<()> {
+    //The edicts short the second output
+    let block = deploy_split(&[(600, 0), (400, 1)], &[(600, 0), (300, 1)])?;
+    assert_eq!(balances(&block)?, (0, 0));
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_premine_splits_must_add_up_to_the_premine() -> Result<()> {
+    let block = deploy_split(&[(600, 0), (300, 1)], &[(600, 0), (300, 1)])?;
+    assert_eq!(balances(&block)?, (0, 0));
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_mint_split_pays_each_output() -> Result<()> {
+    deploy(&params(0, 10_000))?;
+
+    let block = mint_split(
+        &[(700, 0), (300, 1)],
+        &[(700, 0), (300, 1)],
+        START_HEIGHT + 1,
+    )?;
+    assert_eq!(balances(&block)?, (700, 300));
+
+    let supply = return_u128(&call(vec![101], START_HEIGHT + 2)?)?;
+    assert_eq!(supply, 1_000);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_mint_split_short_or_overshooting_is_rejected() -> Result<()> {
+    deploy(&params(0, 10_000))?;
+
+    let bad_edicts: [&[(u128, u32)]; 3] = [
+        //Leaves the second split unpaid
+        &[(700, 0)],
+        //Pays the second split twice
+        &[(700, 0), (300, 1), (300, 1)],
+        //Pays more than the split asks for
+        &[(700, 0), (400, 1)],
+    ];
+
+    for (i, edicts) in bad_edicts.into_iter().enumerate() {
+        let block = mint_split(&[(700, 0), (300, 1)], edicts, START_HEIGHT + 1 + i as u32)?;
+        assert_eq!(balances(&block)?, (0, 0));
+    }
+
+    let supply = return_u128(&call(vec![101], START_HEIGHT + 5)?)?;
+    assert_eq!(supply, 0);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_mint_split_is_bound_by_the_cap() -> Result<()> {
+    deploy(&params(0, 500))?;
+
+    let block = mint_split(
+        &[(400, 0), (200, 1)],
+        &[(400, 0), (200, 1)],
+        START_HEIGHT + 1,
+    )?;
+    assert_eq!(balances(&block)?, (0, 0));
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_mint_split_pointing_at_an_output_is_rejected() -> Result<()> {
+    deploy(&params(0, 10_000))?;
+
+    let params = SchemaMintExactSplitParameters {
+        splits: splits(&[(1_000, 0)]),
+    };
+
+    //The call pays vout #0 straight away, no split protostone is involved
+    let block = index_tx(
+        build_tx(
+            vec![OutPoint::null()],
+            Witness::new(),
+            vec![address_output(&get_address(&ADDRESS1()).to_string())?],
+            vec![message(
+                Cellpack {
+                    target: FORWARDER_ID,
+                    inputs: forward_inputs(TOKEN_ID, to_inputs(121, &borsh::to_vec(&params)?)),
+                },
+                0,
+            )],
+        )?,
+        START_HEIGHT + 1,
+    )?;
+    assert_eq!(alkane_balance_at(&block, 0, TOKEN_ID)?, 0);
+
+    Ok(())
+}
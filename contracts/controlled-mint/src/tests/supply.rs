@@ -0,0 +1,78 @@
+use crate::tests::helpers::{
+    call, call_as_many, deploy, mint, params, return_u128, FORWARDER_ID, START_HEIGHT,
+};
+use anyhow::Result;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+//Premine lands in the checkpoint at START_HEIGHT
+const PREMINE: u128 = 100;
+
+fn supply_at(height: u32, queried_at: u32) -> Result<u128> {
+    let block = call(vec![122, height as u128], queried_at)?;
+    return_u128(&block)
+}
+
+#[wasm_bindgen_test]
+fn test_supply_before_first_checkpoint_is_zero() -> Result<()> {
+    deploy(&params(PREMINE, 1_000))?;
+
+    assert_eq!(supply_at(START_HEIGHT - 1, START_HEIGHT + 1)?, 0);
+    assert_eq!(supply_at(0, START_HEIGHT + 2)?, 0);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_supply_at_exact_checkpoint_height() -> Result<()> {
+    deploy(&params(PREMINE, 1_000))?;
+
+    assert_eq!(supply_at(START_HEIGHT, START_HEIGHT + 1)?, PREMINE);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_mints_in_one_block_collapse_into_one_checkpoint() -> Result<()> {
+    deploy(&params(PREMINE, 1_000))?;
+
+    call_as_many(
+        FORWARDER_ID,
+        vec![vec![106, 5], vec![106, 7]],
+        START_HEIGHT + 1,
+    )?;
+
+    //The block reads as its supply after the last change, not after the first
+    assert_eq!(supply_at(START_HEIGHT + 1, START_HEIGHT + 2)?, PREMINE + 12);
+    assert_eq!(supply_at(START_HEIGHT, START_HEIGHT + 2)?, PREMINE);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_supply_between_and_past_checkpoints() -> Result<()> {
+    deploy(&params(PREMINE, 1_000))?;
+
+    mint(10, START_HEIGHT + 2)?;
+    mint(20, START_HEIGHT + 4)?;
+    mint(30, START_HEIGHT + 6)?;
+
+    assert_eq!(supply_at(START_HEIGHT + 1, START_HEIGHT + 7)?, PREMINE);
+    assert_eq!(supply_at(START_HEIGHT + 3, START_HEIGHT + 8)?, PREMINE + 10);
+    assert_eq!(supply_at(START_HEIGHT + 4, START_HEIGHT + 9)?, PREMINE + 30);
+    assert_eq!(
+        supply_at(START_HEIGHT + 5, START_HEIGHT + 10)?,
+        PREMINE + 30
+    );
+    assert_eq!(
+        supply_at(START_HEIGHT + 6, START_HEIGHT + 11)?,
+        PREMINE + 60
+    );
+
+    //Past the last checkpoint the current supply holds
+    assert_eq!(
+        supply_at(START_HEIGHT + 1_000, START_HEIGHT + 12)?,
+        PREMINE + 60
+    );
+
+    Ok(())
+}
@@ -1,5 +1,5 @@
 //! Anything that is part of the canonical token interface (op-codes
-//! 77, 99-104, 999-1000) lives here so contracts can `impl MintableToken`
+//! 77, 99-104, 120, 122, 999-1000) lives here so contracts can `impl MintableToken`
 //! and get the default behaviour for free.
 
 use alkanes_runtime::{runtime::AlkaneResponder, storage::StoragePointer};
@@ -14,7 +14,12 @@ use metashrew_support::index_pointer::KeyValuePointer;
 use std::io::Cursor;
 use std::u128;
 
-use crate::schemas::SchemaControlledMintInitializationParameters;
+use crate::schemas::{
+    SchemaControlledMintInitializationParameters, SchemaTokenImage, SchemaTokenMetadata,
+};
+
+//Used when a clone was initialized without metadata
+pub const DEFAULT_DECIMALS: u8 = 8;
 
 pub trait MintableToken: AlkaneResponder {
     fn get_consts_pointer(&self) -> StoragePointer {
@@ -33,10 +38,30 @@ pub trait MintableToken: AlkaneResponder {
                     token_symbol: "UNSET".to_string(),
                     premine: 0u128,
                     cap: u128::MAX,
+                    max_mint_per_block: None,
+                    max_mint_per_window: None,
+                    metadata: None,
+                    mint_schedule: None,
+                    premine_splits: None,
                 });
         consts
     }
 
+    fn get_metadata_pointer(&self) -> StoragePointer {
+        StoragePointer::from_keyword("/metadata")
+    }
+
+    fn get_metadata(&self) -> Option<SchemaTokenMetadata> {
+        let metadata_bytes = (*self.get_metadata_pointer().get()).clone();
+        SchemaTokenMetadata::try_from_slice(&metadata_bytes).ok()
+    }
+
+    fn decimals(&self) -> u8 {
+        self.get_metadata()
+            .map(|metadata| metadata.decimals)
+            .unwrap_or(DEFAULT_DECIMALS)
+    }
+
     fn name(&self) -> String {
         self.get_consts().token_name
     }
@@ -52,6 +77,67 @@ pub trait MintableToken: AlkaneResponder {
     }
     fn set_total_supply(&self, v: u128) {
         self.total_supply_pointer().set_value::<u128>(v);
+        self.record_supply_checkpoint(v);
+    }
+
+    //Checkpoint i is (height, total_supply after the last change at that height), heights strictly increase
+    fn supply_checkpoint_count_pointer(&self) -> StoragePointer {
+        StoragePointer::from_keyword("/supply_checkpoints/count")
+    }
+    fn supply_checkpoint_height_pointer(&self, index: u32) -> StoragePointer {
+        StoragePointer::from_keyword("/supply_checkpoints/height")
+            .select(&index.to_le_bytes().to_vec())
+    }
+    fn supply_checkpoint_supply_pointer(&self, index: u32) -> StoragePointer {
+        StoragePointer::from_keyword("/supply_checkpoints/supply")
+            .select(&index.to_le_bytes().to_vec())
+    }
+    fn record_supply_checkpoint(&self, v: u128) {
+        let height = self.height();
+        let mut count_pointer = self.supply_checkpoint_count_pointer();
+        let count = count_pointer.get_value::<u32>();
+
+        //Several supply changes in one block collapse into that block's checkpoint
+        let index = if count > 0
+            && self
+                .supply_checkpoint_height_pointer(count - 1)
+                .get_value::<u64>()
+                == height
+        {
+            count - 1
+        } else {
+            count_pointer.set_value::<u32>(count + 1);
+            self.supply_checkpoint_height_pointer(count)
+                .set_value::<u64>(height);
+            count
+        };
+
+        self.supply_checkpoint_supply_pointer(index)
+            .set_value::<u128>(v);
+    }
+    fn total_supply_at(&self, height: u64) -> u128 {
+        let count = self.supply_checkpoint_count_pointer().get_value::<u32>();
+
+        //First checkpoint above height, the one before it is the supply at height
+        let (mut low, mut high) = (0u32, count);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if self
+                .supply_checkpoint_height_pointer(mid)
+                .get_value::<u64>()
+                <= height
+            {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+
+        if low == 0 {
+            return 0;
+        }
+        self.supply_checkpoint_supply_pointer(low - 1)
+            .get_value::<u128>()
     }
     fn increase_total_supply(&self, v: u128) -> Result<()> {
         self.set_total_supply(
@@ -61,7 +147,15 @@ pub trait MintableToken: AlkaneResponder {
         Ok(())
     }
 
+    //Every mint goes through here, premine included, so this is the one place the cap is enforced
     fn mint(&self, context: &Context, value: u128) -> Result<AlkaneTransfer> {
+        let remaining = self.remaining_mintable();
+        if value > remaining {
+            return Err(anyhow!(
+                "TORTILLA: Mint exceeds cap. Requested: {value}. Remaining: {remaining}"
+            ));
+        }
+
         self.increase_total_supply(value)?;
         Ok(AlkaneTransfer {
             id: context.myself.clone(),
@@ -92,6 +186,30 @@ pub trait MintableToken: AlkaneResponder {
     fn cap(&self) -> u128 {
         self.get_consts().cap
     }
+    //Burned supply still counts against the cap, burning never makes room for new mints
+    fn remaining_mintable(&self) -> u128 {
+        self.cap()
+            .saturating_sub(self.total_supply())
+            .saturating_sub(self.burned())
+    }
+
+    fn burned_pointer(&self) -> StoragePointer {
+        StoragePointer::from_keyword("/burned")
+    }
+    fn burned(&self) -> u128 {
+        self.burned_pointer().get_value::<u128>()
+    }
+    fn burn_supply(&self, v: u128) -> Result<()> {
+        self.set_total_supply(
+            overflow_error(self.total_supply().checked_sub(v))
+                .map_err(|_| anyhow!("total supply underflow"))?,
+        );
+        self.burned_pointer().set_value::<u128>(
+            overflow_error(self.burned().checked_add(v))
+                .map_err(|_| anyhow!("burned counter overflow"))?,
+        );
+        Ok(())
+    }
 
     fn has_tx_hash(&self, txid: &Txid) -> bool {
         StoragePointer::from_keyword("/tx-hashes/")
@@ -148,10 +266,64 @@ pub trait MintableToken: AlkaneResponder {
         rsp.data = self.value_per_mint().to_le_bytes().to_vec();
         Ok(rsp)
     }
+    fn get_total_supply_at(&self, height: u128) -> Result<CallResponse> {
+        let ctx = self.context()?;
+        let mut rsp = CallResponse::forward(&ctx.incoming_alkanes);
+        let height: u64 = height
+            .try_into()
+            .map_err(|_| anyhow!("TORTILLA: height out of range"))?;
+        rsp.data = self.total_supply_at(height).to_le_bytes().to_vec();
+        Ok(rsp)
+    }
+    fn get_burned(&self) -> Result<CallResponse> {
+        let ctx = self.context()?;
+        let mut rsp = CallResponse::forward(&ctx.incoming_alkanes);
+        rsp.data = self.burned().to_le_bytes().to_vec();
+        Ok(rsp)
+    }
+    fn get_remaining_mintable(&self) -> Result<CallResponse> {
+        let ctx = self.context()?;
+        let mut rsp = CallResponse::forward(&ctx.incoming_alkanes);
+        rsp.data = self.remaining_mintable().to_le_bytes().to_vec();
+        Ok(rsp)
+    }
+
+    fn get_decimals(&self) -> Result<CallResponse> {
+        let ctx = self.context()?;
+        let mut rsp = CallResponse::forward(&ctx.incoming_alkanes);
+        rsp.data = (self.decimals() as u128).to_le_bytes().to_vec();
+        Ok(rsp)
+    }
 
-    // 999 / 1000 – data & metadata (no-op for now)
+    // 1000 – inline image bytes when the clone carries one, otherwise a JSON metadata document
     fn get_data(&self) -> Result<CallResponse> {
         let ctx = self.context()?;
-        Ok(CallResponse::forward(&ctx.incoming_alkanes))
+        let mut rsp = CallResponse::forward(&ctx.incoming_alkanes);
+        let metadata = self.get_metadata();
+
+        if let Some(SchemaTokenImage::Inline(image)) = metadata
+            .as_ref()
+            .and_then(|metadata| metadata.image.as_ref())
+        {
+            rsp.data = image.clone();
+            return Ok(rsp);
+        }
+
+        let image_uri = metadata
+            .as_ref()
+            .and_then(|metadata| match &metadata.image {
+                Some(SchemaTokenImage::Uri(uri)) => Some(uri.clone()),
+                _ => None,
+            });
+
+        rsp.data = serde_json::to_vec(&serde_json::json!({
+            "name": self.name(),
+            "symbol": self.symbol(),
+            "decimals": self.decimals(),
+            "description": metadata.as_ref().and_then(|metadata| metadata.description.clone()),
+            "image": image_uri,
+            "external_url": metadata.as_ref().and_then(|metadata| metadata.external_url.clone()),
+        }))?;
+        Ok(rsp)
     }
 }
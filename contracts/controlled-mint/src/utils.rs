@@ -1,3 +1,13 @@
+use crate::schemas::SchemaMintSplit;
+use crate::ControlledMint;
+use alkanes_runtime::runtime::AlkaneResponder;
+use alkanes_support::context::Context;
+use anyhow::{anyhow, ensure, Result};
+use bitcoin::Transaction;
+use ordinals::{Artifact, Runestone};
+use protorune_support::{protostone::Protostone, utils::consensus_decode};
+use std::io::Cursor;
+
 pub fn u128_to_string(v: u128) -> String {
     String::from_utf8(
         v.to_le_bytes()
@@ -23,3 +33,87 @@ pub fn get_byte_array_from_inputs(inputs: &Vec<u128>) -> Vec<u8> {
         .flat_map(|&num| num.to_le_bytes()) // or .to_be_bytes() for big-endian
         .collect()
 }
+
+impl ControlledMint {
+    //A call can only send alkanes to its pointer, so splits are routed by pointing the transaction's
+    //protostone at a later one whose edicts for this token pay exactly the requested splits. Nested
+    //calls share that protostone, so a minter contract that returns the mint gets it split as well.
+    //Same technique as the merkle distributor's batched claims
+    pub fn validate_mint_split(&self, ctx: &Context, splits: &[SchemaMintSplit]) -> Result<()> {
+        let tx = consensus_decode::<Transaction>(&mut Cursor::new(self.transaction()))
+            .map_err(|_| anyhow!("TORTILLA: failed to decode transaction bytes"))?;
+
+        let runestone = match Runestone::decipher(&tx) {
+            Some(Artifact::Runestone(r)) => r,
+            _ => {
+                return Err(anyhow!(
+                    "TORTILLA: transaction does not contain a runestone"
+                ))
+            }
+        };
+
+        let protostones = Protostone::from_runestone(&runestone)
+            .map_err(|e| anyhow!("TORTILLA: failed to parse protostone: {e}"))?;
+
+        let shadow_base = tx.output.len() as u32 + 1;
+
+        let pm_index = ctx
+            .vout
+            .checked_sub(shadow_base)
+            .ok_or_else(|| anyhow!("TORTILLA: vout is not a protomessage index"))?
+            as usize;
+
+        let message = protostones
+            .get(pm_index)
+            .ok_or_else(|| anyhow!("TORTILLA: no protostone message at computed index"))?;
+
+        ensure!(
+            message.edicts.is_empty(),
+            "TORTILLA: protostone message must have zero edicts"
+        );
+
+        let pointer = message
+            .pointer
+            .ok_or_else(|| anyhow!("TORTILLA: protostone message has no pointer"))?;
+
+        let split_index = pointer.checked_sub(shadow_base).ok_or_else(|| {
+            anyhow!("TORTILLA: pointer must target the protostone that splits the mint")
+        })? as usize;
+
+        ensure!(
+            split_index > pm_index,
+            "TORTILLA: split protostone must come after the mint"
+        );
+
+        let split = protostones
+            .get(split_index)
+            .ok_or_else(|| anyhow!("TORTILLA: no protostone at pointer {pointer}"))?;
+
+        let mut remaining: Vec<SchemaMintSplit> = splits.to_vec();
+
+        for edict in split
+            .edicts
+            .iter()
+            .filter(|e| e.id.block == ctx.myself.block && e.id.tx == ctx.myself.tx)
+        {
+            let position = remaining
+                .iter()
+                .position(|s| edict.output == s.output as u128 && edict.amount == s.amount)
+                .ok_or_else(|| {
+                    anyhow!(
+                        "TORTILLA: split edict of {} to output {} does not match a requested split",
+                        edict.amount,
+                        edict.output
+                    )
+                })?;
+            remaining.swap_remove(position);
+        }
+
+        ensure!(
+            remaining.is_empty(),
+            "TORTILLA: split protostone does not pay every requested split"
+        );
+
+        Ok(())
+    }
+}
@@ -1,2 +1,5 @@
 use bitcoin::Network;
 pub const DEPLOYMENT_NETWORK: Network = Network::Regtest;
+
+//Caps one GetClaimedBitmap page at 16,384 leaves
+pub const MAX_BITMAP_WORDS_PER_PAGE: u32 = 64;
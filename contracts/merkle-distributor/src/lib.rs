@@ -1,32 +1,42 @@
 //! Merkle distributor contract
 //!
 //! Created by mork1e
+#![cfg_attr(feature = "no-entrypoint", allow(unused_imports, dead_code))]
 pub mod consts;
 pub mod schemas;
-pub mod token;
 pub mod utils;
 
+#[cfg(test)]
+mod tests;
+
 use alkanes_runtime::{
     declare_alkane, message::MessageDispatch, runtime::AlkaneResponder, storage::StoragePointer,
 };
 use bitcoin::{Address, Transaction};
 
-use alkanes_support::{parcel::AlkaneTransfer, response::CallResponse};
+use alkanes_support::{id::AlkaneId, parcel::AlkaneTransfer, response::CallResponse};
 use anyhow::{anyhow, ensure, Context, Result};
 use borsh::BorshDeserialize;
 use metashrew_support::compat::to_arraybuffer_layout;
 use metashrew_support::index_pointer::KeyValuePointer;
 use metashrew_support::utils::consensus_decode;
 use schemas::SchemaInitializeMerkleDistributorParameters;
+use std::str::FromStr;
 use std::sync::Arc;
-use token::MintableToken;
 
 use utils::{extract_witness_payload, get_byte_array_from_inputs};
 
 use crate::{
-    consts::DEPLOYMENT_NETWORK,
-    schemas::{SchemaAlkaneId, SchemaMerkleLeaf, SchemaMerkleProof},
-    utils::{calc_merkle_root, decode_from_ctx, decode_from_vec},
+    consts::{DEPLOYMENT_NETWORK, MAX_BITMAP_WORDS_PER_PAGE},
+    schemas::{
+        SchemaAlkaneId, SchemaAssetStats, SchemaClaimStatus, SchemaClaimedBitmapParameters,
+        SchemaClaimedBitmapView, SchemaDistributionStats, SchemaDistributorOwner, SchemaMerkleLeaf,
+        SchemaMerkleMultiProof, SchemaMerkleProof, SchemaSignedMerkleClaim,
+    },
+    utils::{
+        calc_claim_intent_digest, calc_merkle_multiproof_root, calc_merkle_root_for_version,
+        decode_from_ctx, decode_from_vec, verify_claim_signature,
+    },
 };
 
 #[derive(Default)]
@@ -43,8 +53,140 @@ impl MerkleDistributor {
         StoragePointer::from_keyword("/merkleroot")
     }
 
-    fn get_used_leaf_pointer(&self, leaf_bytes: &Vec<u8>) -> StoragePointer {
-        StoragePointer::from_keyword("/used").select(leaf_bytes)
+    //Bytes are of type SchemaDistributorOwner, the only one allowed to claw back
+    fn get_owner_pointer(&self) -> StoragePointer {
+        StoragePointer::from_keyword("/owner")
+    }
+
+    //Per asset, keyed by the deposit's position in the initialization params
+    fn get_total_claimed_pointer(&self, asset_index: u32) -> StoragePointer {
+        StoragePointer::from_keyword("/total_claimed").select(&asset_index.to_le_bytes().to_vec())
+    }
+
+    fn get_clawed_back_pointer(&self, asset_index: u32) -> StoragePointer {
+        StoragePointer::from_keyword("/clawed_back").select(&asset_index.to_le_bytes().to_vec())
+    }
+
+    fn get_distributor_owner(&self) -> Result<SchemaDistributorOwner> {
+        SchemaDistributorOwner::try_from_slice(&self.get_owner_pointer().get())
+            .map_err(|_| anyhow!("MERKLE DISTRIBUTOR: Failed to decode owner"))
+    }
+
+    fn get_vout0_address(&self, tx: &Transaction) -> Result<String> {
+        let script_pubkey = &tx
+            .tx_out(0)
+            .context("MERKLE DISTRIBUTOR: vout #0 not present")?
+            .script_pubkey;
+
+        Ok(Address::from_script(script_pubkey, DEPLOYMENT_NETWORK)?.to_string())
+    }
+
+    fn add_total_claimed(&self, asset_index: u32, amount: u128) -> Result<()> {
+        let mut ptr = self.get_total_claimed_pointer(asset_index);
+        let total = ptr
+            .get_value::<u128>()
+            .checked_add(amount)
+            .context("MERKLE DISTRIBUTOR: overflow while tracking total claimed")?;
+        ptr.set_value::<u128>(total);
+        Ok(())
+    }
+
+    //Counts leaves, a multiproof claim adds one per leaf it pays
+    fn get_claim_count_pointer(&self) -> StoragePointer {
+        StoragePointer::from_keyword("/claim_count")
+    }
+
+    fn get_first_claim_height_pointer(&self) -> StoragePointer {
+        StoragePointer::from_keyword("/first_claim_height")
+    }
+
+    fn get_last_claim_height_pointer(&self) -> StoragePointer {
+        StoragePointer::from_keyword("/last_claim_height")
+    }
+
+    fn get_claims_at_height_pointer(&self, height: u64) -> StoragePointer {
+        StoragePointer::from_keyword("/claims_at_height").select(&height.to_le_bytes().to_vec())
+    }
+
+    fn record_claims(&self, leaves: u128) -> Result<()> {
+        let height = self.height();
+
+        let mut ptr_count = self.get_claim_count_pointer();
+        let count = ptr_count.get_value::<u128>();
+        if count == 0 {
+            self.get_first_claim_height_pointer()
+                .set_value::<u64>(height);
+        }
+        ptr_count.set_value::<u128>(
+            count
+                .checked_add(leaves)
+                .context("MERKLE DISTRIBUTOR: overflow while counting claims")?,
+        );
+
+        self.get_last_claim_height_pointer()
+            .set_value::<u64>(height);
+
+        let mut ptr_at_height = self.get_claims_at_height_pointer(height);
+        ptr_at_height.set_value::<u128>(
+            ptr_at_height
+                .get_value::<u128>()
+                .checked_add(leaves)
+                .context("MERKLE DISTRIBUTOR: overflow while counting claims")?,
+        );
+
+        Ok(())
+    }
+
+    //Resolves a leaf's (asset index, amount) pairs against the deposits, skipping zero amounts
+    fn get_leaf_payouts(
+        &self,
+        params: &SchemaInitializeMerkleDistributorParameters,
+        leaf: &SchemaMerkleLeaf,
+    ) -> Result<Vec<(u32, AlkaneId, u128)>> {
+        leaf.amounts
+            .iter()
+            .filter(|entry| entry.amount > 0)
+            .map(|entry| {
+                let deposit = params
+                    .deposits
+                    .get(entry.asset_index as usize)
+                    .with_context(|| {
+                        format!(
+                            "MERKLE DISTRIBUTOR: leaf references unknown asset {}",
+                            entry.asset_index
+                        )
+                    })?;
+                Ok((entry.asset_index, deposit.alkane_id.into(), entry.amount))
+            })
+            .collect()
+    }
+
+    //Claimed state is one bit per leaf index, packed into 256 bit words
+    fn get_claimed_word_pointer(&self, word: u32) -> StoragePointer {
+        StoragePointer::from_keyword("/claimed_bitmap").select(&word.to_le_bytes().to_vec())
+    }
+
+    fn get_claimed_word(&self, word: u32) -> [u8; 32] {
+        let bytes = self.get_claimed_word_pointer(word).get();
+        let mut out = [0u8; 32];
+        if bytes.len() == 32 {
+            out.copy_from_slice(&bytes);
+        }
+        out
+    }
+
+    fn is_leaf_claimed(&self, index: u32) -> bool {
+        let word = self.get_claimed_word(index / 256);
+        let bit = (index % 256) as usize;
+        word[bit / 8] & (1u8 << (bit % 8)) != 0
+    }
+
+    fn set_leaf_claimed(&self, index: u32) {
+        let mut word = self.get_claimed_word(index / 256);
+        let bit = (index % 256) as usize;
+        word[bit / 8] |= 1u8 << (bit % 8);
+        self.get_claimed_word_pointer(index / 256)
+            .set(Arc::new(word.to_vec()));
     }
 
     fn collapse_transfers_for_alkane(
@@ -75,19 +217,26 @@ impl MerkleDistributor {
         Ok(total)
     }
 
-    fn validate_proof(&self, proof: &SchemaMerkleProof) -> Result<bool> {
+    fn get_params(&self) -> Result<SchemaInitializeMerkleDistributorParameters> {
         let params_bytes = {
             let ptr = self.get_merkle_root_pointer();
             // StoragePointer::get() returns Arc<[u8]>, clone to Vec<u8>
             (*ptr.get()).clone()
         };
 
-        let params = decode_from_vec!(params_bytes, SchemaInitializeMerkleDistributorParameters)
-            .context(
-            "MERKLE DISTRIBUTOR: failed to decode initialization params when running proof check",
-        )?;
+        decode_from_vec!(params_bytes, SchemaInitializeMerkleDistributorParameters)
+            .context("MERKLE DISTRIBUTOR: failed to decode initialization params")
+    }
 
-        let root_from_proof = calc_merkle_root(&proof.leaf, &proof.proofs);
+    fn validate_proof(&self, proof: &SchemaMerkleProof) -> Result<bool> {
+        let params = self.get_params()?;
+
+        let root_from_proof = calc_merkle_root_for_version(
+            proof.version,
+            &proof.leaf,
+            &proof.proofs,
+            params.allow_v1_proofs,
+        )?;
 
         let height_u128: u128 = self.height().into();
         let still_in_window = height_u128 <= params.block_end;
@@ -95,41 +244,65 @@ impl MerkleDistributor {
 
         Ok(root_matches && still_in_window)
     }
-}
 
-impl MintableToken for MerkleDistributor {}
+    //A leaf that is in the tree reports Claimed even after block_end, anything unprovable is Invalid
+    fn get_claim_status_for_proof(&self, proof: &SchemaMerkleProof) -> Result<SchemaClaimStatus> {
+        let params = self.get_params()?;
 
-#[derive(MessageDispatch)]
-enum MerkleDistributorMessage {
-    #[opcode(0)]
-    Initialize,
+        let leaf = match decode_from_vec!(proof.leaf, SchemaMerkleLeaf) {
+            Ok(leaf) => leaf,
+            Err(_) => return Ok(SchemaClaimStatus::InvalidOrExpired),
+        };
 
-    #[opcode(77)]
-    MintTokens,
+        let root_matches = calc_merkle_root_for_version(
+            proof.version,
+            &proof.leaf,
+            &proof.proofs,
+            params.allow_v1_proofs,
+        )
+        .map(|root| params.merkle_root == root)
+        .unwrap_or(false);
+
+        if !root_matches {
+            return Ok(SchemaClaimStatus::InvalidOrExpired);
+        }
 
-    #[opcode(99)]
-    #[returns(String)]
-    GetName,
+        if self.is_leaf_claimed(leaf.index) {
+            return Ok(SchemaClaimStatus::Claimed);
+        }
 
-    #[opcode(100)]
-    #[returns(String)]
-    GetSymbol,
+        let height_u128: u128 = self.height().into();
+        if height_u128 > params.block_end {
+            return Ok(SchemaClaimStatus::InvalidOrExpired);
+        }
 
-    #[opcode(101)]
-    #[returns(u128)]
-    GetTotalSupply,
+        Ok(SchemaClaimStatus::Unclaimed)
+    }
 
-    #[opcode(102)]
-    #[returns(u128)]
-    GetCap,
+    fn validate_multiproof(&self, proof: &SchemaMerkleMultiProof) -> Result<bool> {
+        let params = self.get_params()?;
 
-    #[opcode(103)]
-    #[returns(u128)]
-    GetMinted,
+        let root_from_proof = calc_merkle_multiproof_root(
+            proof.version,
+            proof.leaf_count,
+            &proof.indices,
+            &proof.leaves,
+            &proof.proofs,
+            params.allow_v1_proofs,
+        )?;
 
-    #[opcode(104)]
-    #[returns(u128)]
-    GetValuePerMint,
+        let height_u128: u128 = self.height().into();
+        let still_in_window = height_u128 <= params.block_end;
+        let root_matches = params.merkle_root == root_from_proof;
+
+        Ok(root_matches && still_in_window)
+    }
+}
+
+#[derive(MessageDispatch)]
+enum MerkleDistributorMessage {
+    #[opcode(0)]
+    Initialize,
 
     #[opcode(105)]
     GetIsValidClaim,
@@ -140,6 +313,31 @@ enum MerkleDistributorMessage {
     #[opcode(107)]
     GetInitializationParams,
 
+    #[opcode(108)]
+    ClaimSigned,
+
+    #[opcode(109)]
+    ClaimMultiple,
+
+    #[opcode(110)]
+    GetClaimedBitmap,
+
+    #[opcode(111)]
+    GetClaimStatus,
+
+    #[opcode(112)]
+    Clawback,
+
+    #[opcode(113)]
+    GetOwner,
+
+    #[opcode(114)]
+    GetDistributionStats,
+
+    #[opcode(115)]
+    #[returns(u128)]
+    GetClaimsAtHeight { height: u128 },
+
     #[opcode(1000)]
     #[returns(Vec<u8>)]
     GetData,
@@ -156,24 +354,63 @@ impl MerkleDistributor {
         let params = decode_from_ctx!(&context, SchemaInitializeMerkleDistributorParameters)?;
         let mut ptr_merkle_root = self.get_merkle_root_pointer();
 
-        let amount_passed_in =
-            self.collapse_transfers_for_alkane(params.alkane_id, &mut response)?;
-
-        let amount_expected = params.amount;
-
         ensure!(
-            amount_passed_in >= params.amount,
-            "MERKLE DISTRIBUTOR: Attempted to start merkle distributor contract with an amount greater than what was present in alkane transfers. Passed in: {amount_passed_in}. Expected: {amount_expected}"
+            !params.deposits.is_empty(),
+            "MERKLE DISTRIBUTOR: At least one deposit is required"
         );
 
-        let refund_amount = amount_passed_in.saturating_sub(params.amount);
+        for (i, deposit) in params.deposits.iter().enumerate() {
+            ensure!(
+                params.deposits[..i]
+                    .iter()
+                    .all(|other| other.alkane_id != deposit.alkane_id),
+                "MERKLE DISTRIBUTOR: Each alkane can only be deposited once"
+            );
 
-        if refund_amount > 0 {
-            response.alkanes.0.push(AlkaneTransfer {
-                id: params.alkane_id.into(),
-                value: refund_amount,
-            })
+            let amount_passed_in =
+                self.collapse_transfers_for_alkane(deposit.alkane_id, &mut response)?;
+
+            let amount_expected = deposit.amount;
+
+            ensure!(
+                amount_passed_in >= deposit.amount,
+                "MERKLE DISTRIBUTOR: Attempted to start merkle distributor contract with an amount greater than what was present in alkane transfers. Passed in: {amount_passed_in}. Expected: {amount_expected}"
+            );
+
+            let refund_amount = amount_passed_in.saturating_sub(deposit.amount);
+
+            if refund_amount > 0 {
+                response.alkanes.0.push(AlkaneTransfer {
+                    id: deposit.alkane_id.into(),
+                    value: refund_amount,
+                })
+            }
         }
+
+        let owner = match &params.owner_address {
+            Some(address) => {
+                let address = Address::from_str(address)
+                    .ok()
+                    .and_then(|address| address.require_network(DEPLOYMENT_NETWORK).ok())
+                    .context("MERKLE DISTRIBUTOR: owner_address is not a valid address")?;
+                SchemaDistributorOwner::Address(address.to_string())
+            }
+            None => {
+                //A transaction's caller is 0:0, which anyone can call as
+                ensure!(
+                    context.caller.block != 0 || context.caller.tx != 0,
+                    "MERKLE DISTRIBUTOR: owner_address is required when initializing from a transaction"
+                );
+                SchemaDistributorOwner::Alkane(SchemaAlkaneId {
+                    block: context.caller.block.try_into()?,
+                    tx: context.caller.tx.try_into()?,
+                })
+            }
+        };
+
+        self.get_owner_pointer()
+            .set(Arc::new(borsh::to_vec(&owner)?));
+
         ptr_merkle_root.set(Arc::new(borsh::to_vec(&params).context(
             "MERKLE DISTRIBUTOR: failed to encode merkle distributor params",
         )?));
@@ -187,20 +424,24 @@ impl MerkleDistributor {
 
         // decode the caller‑supplied proof (Borsh‑encoded in `ctx`)
         let merkle_proof = decode_from_ctx!(&ctx, SchemaMerkleProof)?;
-        let ptr_used_leaf = self.get_used_leaf_pointer(&merkle_proof.leaf);
-        let used_leaf_check = ptr_used_leaf.get_value::<u8>();
-
-        ensure!(
-            used_leaf_check != 0u8,
-            "MERKLE DISTRIBUTOR: This leaf has already been used to claim"
-        );
-        let ok = self.validate_proof(&merkle_proof)?;
+        let ok = self.get_claim_status_for_proof(&merkle_proof)? == SchemaClaimStatus::Unclaimed;
 
         // push u128 {1|0} as return value
         resp.data = if ok { vec![1u8] } else { vec![0u8] };
         Ok(resp)
     }
 
+    fn get_claim_status(&self) -> Result<CallResponse> {
+        let ctx = self.context()?;
+        let mut response = CallResponse::forward(&ctx.incoming_alkanes);
+
+        let merkle_proof = decode_from_ctx!(&ctx, SchemaMerkleProof)?;
+        let status = self.get_claim_status_for_proof(&merkle_proof)?;
+
+        response.data = borsh::to_vec(&status)?;
+        Ok(response)
+    }
+
     fn get_initialization_params(&self) -> Result<CallResponse> {
         let ctx = self.context()?;
         let mut response = CallResponse::forward(&ctx.incoming_alkanes);
@@ -232,19 +473,6 @@ impl MerkleDistributor {
         let merkle_proof = decode_from_vec!(witness_payload, SchemaMerkleProof)
             .context("MERKLE DISTRIBUTOR: Failed to decode merkle proof from witness data")?;
 
-        ensure!(
-            self.validate_proof(&merkle_proof)?,
-            "MERKLE DISTRIBUTOR: Merkle proof check failed",
-        );
-
-        let mut ptr_used_leaf = self.get_used_leaf_pointer(&merkle_proof.leaf);
-        let used_leaf_check = ptr_used_leaf.get_value::<u8>();
-
-        ensure!(
-            used_leaf_check != 0u8,
-            "MERKLE DISTRIBUTOR: This leaf has already been used to claim"
-        );
-
         let leaf = decode_from_vec!(merkle_proof.leaf, SchemaMerkleLeaf)?;
 
         let caller_script_pub_key = tx
@@ -260,21 +488,337 @@ impl MerkleDistributor {
             "MERKLE DISTRIBUTOR: vout #0 doesnt contain the address in merkle proof"
         );
 
-        let params_bytes = {
-            let ptr = self.get_merkle_root_pointer();
-            (*ptr.get()).clone()
+        self.claim_leaf(&merkle_proof, &mut response)?;
+
+        Ok(response)
+    }
+
+    fn claim_signed(&self) -> Result<CallResponse> {
+        let ctx = self.context()?;
+        let mut response = CallResponse::forward(&ctx.incoming_alkanes);
+
+        let tx = self
+            .get_serialized_transaction()
+            .map_err(|_| anyhow!("MERKLE DISTRIBUTOR: Failed to decode tx"))?;
+
+        let witness_payload = match extract_witness_payload(&tx) {
+            Some(bytes) => bytes,
+            None => return Err(anyhow!("MERKLE DISTRIBUTOR: Failed to decode tx")),
+        };
+
+        let signed_claim = decode_from_vec!(witness_payload, SchemaSignedMerkleClaim)
+            .context("MERKLE DISTRIBUTOR: Failed to decode signed claim from witness data")?;
+
+        //Claimed alkanes go wherever the protostone points, so it has to be the signed output
+        self.validate_protostone_tx_pointer(&ctx, signed_claim.recipient_vout)?;
+
+        let recipient_script = tx
+            .tx_out(signed_claim.recipient_vout as usize)
+            .context("MERKLE DISTRIBUTOR: recipient output not present")?
+            .script_pubkey
+            .clone();
+
+        let leaf = decode_from_vec!(signed_claim.proof.leaf, SchemaMerkleLeaf)?;
+        let leaf_address = Address::from_str(&leaf.address)
+            .context("MERKLE DISTRIBUTOR: invalid address in merkle leaf")?
+            .require_network(DEPLOYMENT_NETWORK)
+            .context("MERKLE DISTRIBUTOR: merkle leaf address is for the wrong network")?;
+
+        let digest = calc_claim_intent_digest(
+            &ctx.myself,
+            &signed_claim.proof.leaf,
+            recipient_script.as_bytes(),
+        );
+
+        verify_claim_signature(
+            &leaf_address,
+            digest,
+            &signed_claim.pubkey,
+            &signed_claim.signature,
+        )?;
+
+        self.claim_leaf(&signed_claim.proof, &mut response)?;
+
+        Ok(response)
+    }
+
+    fn get_claimed_bitmap(&self) -> Result<CallResponse> {
+        let ctx = self.context()?;
+        let mut response = CallResponse::forward(&ctx.incoming_alkanes);
+
+        let params = decode_from_ctx!(&ctx, SchemaClaimedBitmapParameters)?;
+        let count = params.count.min(MAX_BITMAP_WORDS_PER_PAGE);
+
+        let words = (params.start_word..params.start_word.saturating_add(count))
+            .map(|word| self.get_claimed_word(word))
+            .collect();
+
+        response.data = borsh::to_vec(&SchemaClaimedBitmapView {
+            start_word: params.start_word,
+            words,
+        })?;
+
+        Ok(response)
+    }
+
+    //Claims several leaves with one multiproof, each paid to the output carrying its address
+    fn claim_multiple(&self) -> Result<CallResponse> {
+        let ctx = self.context()?;
+        let mut response = CallResponse::forward(&ctx.incoming_alkanes);
+
+        let tx = self
+            .get_serialized_transaction()
+            .map_err(|_| anyhow!("MERKLE DISTRIBUTOR: Failed to decode tx"))?;
+
+        let witness_payload = match extract_witness_payload(&tx) {
+            Some(bytes) => bytes,
+            None => return Err(anyhow!("MERKLE DISTRIBUTOR: Failed to decode tx")),
         };
 
-        let params = decode_from_vec!(params_bytes, SchemaInitializeMerkleDistributorParameters)
-            .context(
-                "MERKLE DISTRIBUTOR: failed to decode initialization params when running claim",
-            )?;
+        let multiproof = decode_from_vec!(witness_payload, SchemaMerkleMultiProof)
+            .context("MERKLE DISTRIBUTOR: Failed to decode multiproof from witness data")?;
+
+        ensure!(
+            multiproof.recipient_vouts.len() == multiproof.leaves.len(),
+            "MERKLE DISTRIBUTOR: multiproof needs one recipient output per leaf"
+        );
+
+        ensure!(
+            self.validate_multiproof(&multiproof)?,
+            "MERKLE DISTRIBUTOR: Merkle multiproof check failed",
+        );
+
+        let params = self.get_params()?;
+
+        let mut payouts: Vec<(AlkaneId, u32, u128)> = Vec::new();
+        let mut totals: Vec<u128> = vec![0; params.deposits.len()];
+
+        for (leaf_bytes, vout) in multiproof.leaves.iter().zip(&multiproof.recipient_vouts) {
+            let leaf = decode_from_vec!(leaf_bytes, SchemaMerkleLeaf)?;
+
+            let recipient_script = tx
+                .tx_out(*vout as usize)
+                .context("MERKLE DISTRIBUTOR: recipient output not present")?
+                .script_pubkey
+                .clone();
+
+            let recipient_address = Address::from_script(&recipient_script, DEPLOYMENT_NETWORK)?;
+
+            ensure!(
+                recipient_address.to_string() == leaf.address,
+                "MERKLE DISTRIBUTOR: vout #{vout} doesnt contain the address in merkle leaf"
+            );
+
+            //Set as we go so a leaf repeated within the batch is caught too
+            ensure!(
+                !self.is_leaf_claimed(leaf.index),
+                "MERKLE DISTRIBUTOR: This leaf has already been used to claim"
+            );
+            self.set_leaf_claimed(leaf.index);
+
+            for (asset_index, alkane_id, amount) in self.get_leaf_payouts(&params, &leaf)? {
+                totals[asset_index as usize] = totals[asset_index as usize]
+                    .checked_add(amount)
+                    .context("MERKLE DISTRIBUTOR: overflow while summing claimed amounts")?;
+                payouts.push((alkane_id, *vout, amount));
+            }
+        }
+
+        self.validate_protostone_split(&ctx, &payouts)?;
+        self.record_claims(multiproof.leaves.len() as u128)?;
+
+        for (asset_index, total) in totals.into_iter().enumerate() {
+            if total == 0 {
+                continue;
+            }
+            self.add_total_claimed(asset_index as u32, total)?;
+            response.alkanes.0.push(AlkaneTransfer {
+                id: params.deposits[asset_index].alkane_id.into(),
+                value: total,
+            });
+        }
+
+        Ok(response)
+    }
+
+    //Shared by both claim modes once the caller has proven they can act for the leaf
+    fn claim_leaf(
+        &self,
+        merkle_proof: &SchemaMerkleProof,
+        response: &mut CallResponse,
+    ) -> Result<()> {
+        ensure!(
+            self.validate_proof(merkle_proof)?,
+            "MERKLE DISTRIBUTOR: Merkle proof check failed",
+        );
+
+        let leaf = decode_from_vec!(merkle_proof.leaf, SchemaMerkleLeaf)?;
+
+        ensure!(
+            !self.is_leaf_claimed(leaf.index),
+            "MERKLE DISTRIBUTOR: This leaf has already been used to claim"
+        );
+
+        let params = self.get_params()?;
+
+        self.set_leaf_claimed(leaf.index);
+        self.record_claims(1)?;
+
+        for (asset_index, alkane_id, amount) in self.get_leaf_payouts(&params, &leaf)? {
+            self.add_total_claimed(asset_index, amount)?;
+            response.alkanes.0.push(AlkaneTransfer {
+                id: alkane_id,
+                value: amount,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn get_owner(&self) -> Result<CallResponse> {
+        let ctx = self.context()?;
+        let mut response = CallResponse::forward(&ctx.incoming_alkanes);
+
+        //Bytes are of type SchemaDistributorOwner
+        response.data = (*self.get_owner_pointer().get()).clone();
+        Ok(response)
+    }
+
+    fn get_distribution_stats(&self) -> Result<CallResponse> {
+        let ctx = self.context()?;
+        let mut response = CallResponse::forward(&ctx.incoming_alkanes);
+
+        let params = self.get_params()?;
+
+        let assets = params
+            .deposits
+            .iter()
+            .enumerate()
+            .map(|(asset_index, deposit)| {
+                let asset_index = asset_index as u32;
+                let claimed = self
+                    .get_total_claimed_pointer(asset_index)
+                    .get_value::<u128>();
+                let clawed_back = self
+                    .get_clawed_back_pointer(asset_index)
+                    .get_value::<u128>();
+
+                let remaining = deposit
+                    .amount
+                    .checked_sub(claimed)
+                    .and_then(|left| left.checked_sub(clawed_back))
+                    .context("MERKLE DISTRIBUTOR: paid out more than was deposited")?;
+
+                Ok(SchemaAssetStats {
+                    alkane_id: deposit.alkane_id,
+                    deposited: deposit.amount,
+                    claimed,
+                    clawed_back,
+                    remaining,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        response.data = borsh::to_vec(&SchemaDistributionStats {
+            claim_count: self.get_claim_count_pointer().get_value::<u128>(),
+            first_claim_height: self.get_first_claim_height_pointer().get_value::<u64>(),
+            last_claim_height: self.get_last_claim_height_pointer().get_value::<u64>(),
+            assets,
+        })?;
+
+        Ok(response)
+    }
+
+    fn get_claims_at_height(&self, height: u128) -> Result<CallResponse> {
+        let ctx = self.context()?;
+        let mut response = CallResponse::forward(&ctx.incoming_alkanes);
+
+        let height: u64 = height
+            .try_into()
+            .context("MERKLE DISTRIBUTOR: height out of range")?;
+
+        response.data = self
+            .get_claims_at_height_pointer(height)
+            .get_value::<u128>()
+            .to_le_bytes()
+            .to_vec();
+
+        Ok(response)
+    }
+
+    fn get_data(&self) -> Result<CallResponse> {
+        let ctx = self.context()?;
+        Ok(CallResponse::forward(&ctx.incoming_alkanes))
+    }
+
+    //Once the window closes nothing else can be claimed, so deposit - total_claimed is exactly what's left
+    fn clawback(&self) -> Result<CallResponse> {
+        let ctx = self.context()?;
+        let mut response = CallResponse::forward(&ctx.incoming_alkanes);
+
+        match self.get_distributor_owner()? {
+            SchemaDistributorOwner::Alkane(owner) => ensure!(
+                ctx.caller == owner.into(),
+                "MERKLE DISTRIBUTOR: Caller is not the owner"
+            ),
+            //Called from a contract the remainder would go to that contract, not to vout #0
+            SchemaDistributorOwner::Address(owner) => {
+                ensure!(
+                    ctx.caller.block == 0 && ctx.caller.tx == 0,
+                    "MERKLE DISTRIBUTOR: An address owner has to claw back from a transaction"
+                );
+                self.validate_protostone_tx(&ctx)?;
+
+                let tx = self
+                    .get_serialized_transaction()
+                    .map_err(|_| anyhow!("MERKLE DISTRIBUTOR: Failed to decode tx"))?;
+
+                ensure!(
+                    self.get_vout0_address(&tx)? == owner,
+                    "MERKLE DISTRIBUTOR: vout #0 is not the owner"
+                );
+            }
+        }
+
+        let params = self.get_params()?;
+
+        let height_u128: u128 = self.height().into();
+        ensure!(
+            height_u128 > params.block_end,
+            "MERKLE DISTRIBUTOR: Clawback is only allowed after block_end"
+        );
+
+        let mut clawed_any = false;
+
+        for (asset_index, deposit) in params.deposits.iter().enumerate() {
+            let asset_index = asset_index as u32;
+
+            let mut ptr_clawed_back = self.get_clawed_back_pointer(asset_index);
+            if ptr_clawed_back.get_value::<u128>() != 0 {
+                continue;
+            }
+
+            let remainder = deposit
+                .amount
+                .checked_sub(
+                    self.get_total_claimed_pointer(asset_index)
+                        .get_value::<u128>(),
+                )
+                .context("MERKLE DISTRIBUTOR: claimed more than was deposited")?;
+
+            if remainder == 0 {
+                continue;
+            }
+
+            ptr_clawed_back.set_value::<u128>(remainder);
+            response.alkanes.0.push(AlkaneTransfer {
+                id: deposit.alkane_id.into(),
+                value: remainder,
+            });
+            clawed_any = true;
+        }
 
-        ptr_used_leaf.set_value(1u8);
-        response.alkanes.0.push(AlkaneTransfer {
-            id: params.alkane_id.into(),
-            value: leaf.amount,
-        });
+        ensure!(clawed_any, "MERKLE DISTRIBUTOR: Nothing left to claw back");
 
         Ok(response)
     }
@@ -283,6 +827,7 @@ impl MerkleDistributor {
 impl AlkaneResponder for MerkleDistributor {}
 
 // Use the MessageDispatch macro for opcode handling
+#[cfg(not(feature = "no-entrypoint"))]
 declare_alkane! {
     impl AlkaneResponder for MerkleDistributor {
         type Message = MerkleDistributorMessage;
@@ -8,11 +8,28 @@ pub struct SchemaAlkaneId {
 }
 
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
-pub struct SchemaInitializeMerkleDistributorParameters {
-    pub merkle_root: Vec<u8>,
+pub struct SchemaDistributorDeposit {
     pub alkane_id: SchemaAlkaneId,
     pub amount: u128,
+}
+
+//Leaves refer to deposits by their position in this vector
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct SchemaInitializeMerkleDistributorParameters {
+    pub merkle_root: Vec<u8>,
+    pub deposits: Vec<SchemaDistributorDeposit>,
     pub block_end: u128,
+    pub allow_v1_proofs: bool,
+    //Becomes the owner instead of the caller. Required when initializing straight from a
+    //transaction, where the caller is 0:0
+    pub owner_address: Option<String>,
+}
+
+//Who may claw back: the initializing contract, or an address for distributors set up from a transaction
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub enum SchemaDistributorOwner {
+    Alkane(SchemaAlkaneId),
+    Address(String),
 }
 impl From<SchemaAlkaneId> for AlkaneId {
     fn from(value: SchemaAlkaneId) -> Self {
@@ -25,12 +42,79 @@ impl From<SchemaAlkaneId> for AlkaneId {
 
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
 pub struct SchemaMerkleProof {
+    pub version: u8,
     pub leaf: Vec<u8>,
     pub proofs: Vec<Vec<u8>>,
 }
 
+//Lets the leaf owner route their claim to any output by signing over it
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct SchemaSignedMerkleClaim {
+    pub proof: SchemaMerkleProof,
+    pub recipient_vout: u32,
+    pub pubkey: Vec<u8>,
+    pub signature: Vec<u8>,
+}
+
+//One proof for several leaves. recipient_vouts[i] is the output that carries leaves[i]'s address
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct SchemaMerkleMultiProof {
+    pub version: u8,
+    pub leaf_count: u32,
+    pub indices: Vec<u32>,
+    pub leaves: Vec<Vec<u8>>,
+    pub proofs: Vec<Vec<u8>>,
+    pub recipient_vouts: Vec<u32>,
+}
+
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
 pub struct SchemaMerkleLeaf {
+    pub index: u32,
     pub address: String,
+    pub amounts: Vec<SchemaLeafAmount>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct SchemaLeafAmount {
+    pub asset_index: u32,
     pub amount: u128,
 }
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, Copy)]
+pub enum SchemaClaimStatus {
+    Unclaimed,
+    Claimed,
+    InvalidOrExpired,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct SchemaClaimedBitmapParameters {
+    pub start_word: u32,
+    pub count: u32,
+}
+
+//Word n covers leaf indices n*256..n*256+255, bit i of byte j is index n*256 + j*8 + i
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct SchemaClaimedBitmapView {
+    pub start_word: u32,
+    pub words: Vec<[u8; 32]>,
+}
+
+//Per deposit, in the same order as the initialization params
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct SchemaAssetStats {
+    pub alkane_id: SchemaAlkaneId,
+    pub deposited: u128,
+    pub claimed: u128,
+    pub clawed_back: u128,
+    pub remaining: u128,
+}
+
+//Heights are 0 until the first claim lands
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct SchemaDistributionStats {
+    pub claim_count: u128,
+    pub first_claim_height: u64,
+    pub last_claim_height: u64,
+    pub assets: Vec<SchemaAssetStats>,
+}
@@ -0,0 +1,97 @@
+use crate::schemas::SchemaClaimStatus;
+use crate::tests::helpers::{claim, claim_status, setup, token_balance, Airdrop, START_HEIGHT};
+use anyhow::Result;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn test_first_claim_pays_leaf_amount() -> Result<()> {
+    let airdrop = Airdrop::new()?;
+    setup(&airdrop, (START_HEIGHT + 100) as u128)?;
+
+    let block = claim(&airdrop.proof(0), START_HEIGHT + 2)?;
+    assert_eq!(token_balance(&block)?, 1_000);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_double_claim_is_rejected() -> Result<()> {
+    let airdrop = Airdrop::new()?;
+    setup(&airdrop, (START_HEIGHT + 100) as u128)?;
+
+    let first = claim(&airdrop.proof(0), START_HEIGHT + 2)?;
+    assert_eq!(token_balance(&first)?, 1_000);
+
+    let second = claim(&airdrop.proof(0), START_HEIGHT + 3)?;
+    assert_eq!(token_balance(&second)?, 0);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_claim_to_wrong_address_is_rejected() -> Result<()> {
+    let airdrop = Airdrop::new()?;
+    setup(&airdrop, (START_HEIGHT + 100) as u128)?;
+
+    //Leaf 1 belongs to OTHER_ADDRESS but vout #0 is ADDRESS1
+    let block = claim(&airdrop.proof(1), START_HEIGHT + 2)?;
+    assert_eq!(token_balance(&block)?, 0);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_claim_after_block_end_is_rejected() -> Result<()> {
+    let airdrop = Airdrop::new()?;
+    setup(&airdrop, (START_HEIGHT + 2) as u128)?;
+
+    let block = claim(&airdrop.proof(0), START_HEIGHT + 3)?;
+    assert_eq!(token_balance(&block)?, 0);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_claim_status_before_and_after_claim() -> Result<()> {
+    let airdrop = Airdrop::new()?;
+    setup(&airdrop, (START_HEIGHT + 100) as u128)?;
+
+    assert_eq!(
+        claim_status(&airdrop.proof(0), START_HEIGHT + 2)?,
+        SchemaClaimStatus::Unclaimed
+    );
+
+    claim(&airdrop.proof(0), START_HEIGHT + 3)?;
+
+    assert_eq!(
+        claim_status(&airdrop.proof(0), START_HEIGHT + 4)?,
+        SchemaClaimStatus::Claimed
+    );
+    //The other leaf is untouched by the claim
+    assert_eq!(
+        claim_status(&airdrop.proof(1), START_HEIGHT + 4)?,
+        SchemaClaimStatus::Unclaimed
+    );
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_claim_status_rejects_bad_proof_and_expiry() -> Result<()> {
+    let airdrop = Airdrop::new()?;
+    setup(&airdrop, (START_HEIGHT + 3) as u128)?;
+
+    let mut forged = airdrop.proof(0);
+    forged.proofs = vec![[0u8; 32].to_vec()];
+    assert_eq!(
+        claim_status(&forged, START_HEIGHT + 2)?,
+        SchemaClaimStatus::InvalidOrExpired
+    );
+
+    assert_eq!(
+        claim_status(&airdrop.proof(0), START_HEIGHT + 4)?,
+        SchemaClaimStatus::InvalidOrExpired
+    );
+
+    Ok(())
+}
@@ -0,0 +1,195 @@
+use crate::schemas::SchemaInitializeMerkleDistributorParameters;
+use crate::tests::helpers::{
+    call_distributor, claim, distribution_stats, distributor_params, funding_outpoints,
+    mint_funding, setup, setup_with, token_balance, vout0_address, Airdrop, FUNDED_AMOUNT,
+    OTHER_ADDRESS, START_HEIGHT,
+};
+use crate::tests::shared::{address_output, build_tx, index_tx, message, to_inputs};
+use crate::tests::std::{merkle_distributor_build, test_forwarder_build};
+use alkanes::indexer::index_block;
+use alkanes::tests::helpers as alkane_helpers;
+use alkanes_support::cellpack::Cellpack;
+use alkanes_support::envelope::RawEnvelope;
+use alkanes_support::id::AlkaneId;
+use anyhow::Result;
+use bitcoin::{Block, Witness};
+use protorune::test_helpers::create_block_with_coinbase_tx;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+//The contract owned setup deploys these after the funding tokens and their auth tokens
+const FORWARDER_ID: AlkaneId = AlkaneId { block: 2, tx: 4 };
+const TEMPLATE_ID: AlkaneId = AlkaneId { block: 2, tx: 5 };
+const CLONE_ID: AlkaneId = AlkaneId { block: 2, tx: 6 };
+
+fn clawback(height: u32) -> Result<Block> {
+    call_distributor(vec![112], Witness::new(), height)
+}
+
+fn forward(target: AlkaneId, inputs: Vec<u128>) -> Cellpack {
+    let mut forwarded = vec![1, target.block, target.tx];
+    forwarded.extend(inputs);
+    Cellpack {
+        target: FORWARDER_ID,
+        inputs: forwarded,
+    }
+}
+
+//The forwarder clones an uninitialized distributor and initializes it, escrowing the funding, so
+//the forwarder is the caller that becomes owner
+fn setup_owned_by_contract(params: &SchemaInitializeMerkleDistributorParameters) -> Result<()> {
+    let token_block = mint_funding()?;
+
+    let mut deploy_block = create_block_with_coinbase_tx(START_HEIGHT + 1);
+    for (binary, inputs) in [
+        (test_forwarder_build::get_bytes(), vec![0]),
+        //GetOwner just reads storage, so the template deploys without initializing
+        (merkle_distributor_build::get_bytes(), vec![113]),
+    ] {
+        deploy_block
+            .txdata
+            .push(alkane_helpers::create_multiple_cellpack_with_witness(
+                RawEnvelope::from(binary).to_witness(true),
+                vec![Cellpack {
+                    target: AlkaneId { block: 1, tx: 0 },
+                    inputs,
+                }],
+                false,
+            ));
+    }
+    index_block(&deploy_block, START_HEIGHT + 1)?;
+
+    index_tx(
+        build_tx(
+            funding_outpoints(&token_block),
+            Witness::new(),
+            vec![address_output(&vout0_address())?],
+            vec![message(
+                forward(
+                    AlkaneId {
+                        block: 5,
+                        tx: TEMPLATE_ID.tx,
+                    },
+                    to_inputs(0, &borsh::to_vec(params)?),
+                ),
+                0,
+            )],
+        )?,
+        START_HEIGHT + 2,
+    )?;
+
+    Ok(())
+}
+
+fn call(cellpack: Cellpack, height: u32) -> Result<Block> {
+    let mut block = create_block_with_coinbase_tx(height);
+    block
+        .txdata
+        .push(alkane_helpers::create_multiple_cellpack_with_witness(
+            Witness::new(),
+            vec![cellpack],
+            false,
+        ));
+    index_block(&block, height)?;
+    Ok(block)
+}
+
+#[wasm_bindgen_test]
+fn test_clawback_returns_the_unclaimed_remainder() -> Result<()> {
+    let airdrop = Airdrop::new()?;
+    setup(&airdrop, (START_HEIGHT + 10) as u128)?;
+
+    let claimed = claim(&airdrop.proof(0), START_HEIGHT + 2)?;
+    assert_eq!(token_balance(&claimed)?, 1_000);
+
+    let clawed = clawback(START_HEIGHT + 11)?;
+    assert_eq!(token_balance(&clawed)?, FUNDED_AMOUNT - 1_000);
+
+    let stats = distribution_stats(START_HEIGHT + 12)?;
+    assert_eq!(stats.assets[0].claimed, 1_000);
+    assert_eq!(stats.assets[0].clawed_back, FUNDED_AMOUNT - 1_000);
+    assert_eq!(stats.assets[0].remaining, 0);
+
+    //Nothing is left for a second clawback
+    let again = clawback(START_HEIGHT + 13)?;
+    assert_eq!(token_balance(&again)?, 0);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_clawback_before_block_end_is_rejected() -> Result<()> {
+    let airdrop = Airdrop::new()?;
+    setup(&airdrop, (START_HEIGHT + 10) as u128)?;
+
+    let early = clawback(START_HEIGHT + 5)?;
+    assert_eq!(token_balance(&early)?, 0);
+
+    //block_end itself is still inside the claim window
+    let at_end = clawback(START_HEIGHT + 10)?;
+    assert_eq!(token_balance(&at_end)?, 0);
+
+    let after_end = clawback(START_HEIGHT + 11)?;
+    assert_eq!(token_balance(&after_end)?, FUNDED_AMOUNT);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_clawback_by_non_owner_is_rejected() -> Result<()> {
+    let airdrop = Airdrop::new()?;
+    let mut params = distributor_params(&airdrop, (START_HEIGHT + 10) as u128);
+    params.owner_address = Some(OTHER_ADDRESS.to_string());
+    setup_with(&params)?;
+
+    //vout #0 of the helper transactions is ADDRESS1, not the owner
+    let block = clawback(START_HEIGHT + 11)?;
+    assert_eq!(token_balance(&block)?, 0);
+
+    let stats = distribution_stats(START_HEIGHT + 12)?;
+    assert_eq!(stats.assets[0].clawed_back, 0);
+    assert_eq!(stats.assets[0].remaining, FUNDED_AMOUNT);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_initialize_from_a_transaction_needs_an_owner_address() -> Result<()> {
+    let airdrop = Airdrop::new()?;
+
+    for owner_address in [None, Some("not an address".to_string())] {
+        let mut params = distributor_params(&airdrop, (START_HEIGHT + 10) as u128);
+        params.owner_address = owner_address;
+
+        //Initialize fails and the funding is refunded to vout #0
+        let deploy_block = setup_with(&params)?;
+        assert_eq!(token_balance(&deploy_block)?, FUNDED_AMOUNT);
+
+        let block = claim(&airdrop.proof(0), START_HEIGHT + 2)?;
+        assert_eq!(token_balance(&block)?, 0);
+    }
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_initializing_contract_owns_the_distributor() -> Result<()> {
+    let airdrop = Airdrop::new()?;
+    let mut params = distributor_params(&airdrop, (START_HEIGHT + 10) as u128);
+    params.owner_address = None;
+    setup_owned_by_contract(&params)?;
+
+    //A plain transaction isn't the owner, even with no owner_address set
+    let direct = call(
+        Cellpack {
+            target: CLONE_ID,
+            inputs: vec![112],
+        },
+        START_HEIGHT + 11,
+    )?;
+    assert_eq!(token_balance(&direct)?, 0);
+
+    let through_owner = call(forward(CLONE_ID, vec![112]), START_HEIGHT + 12)?;
+    assert_eq!(token_balance(&through_owner)?, FUNDED_AMOUNT);
+
+    Ok(())
+}
@@ -0,0 +1,272 @@
+use crate::schemas::{
+    SchemaAlkaneId, SchemaClaimStatus, SchemaDistributionStats, SchemaDistributorDeposit,
+    SchemaInitializeMerkleDistributorParameters, SchemaLeafAmount, SchemaMerkleLeaf,
+    SchemaMerkleMultiProof, SchemaMerkleProof,
+};
+use crate::tests::shared::{
+    address_output, alkane_balance, build_tx, index_tx, message, return_data, split, to_inputs,
+};
+use crate::tests::std::merkle_distributor_build;
+use crate::utils::{tagged_hash, MERKLE_LEAF_TAG, MERKLE_NODE_TAG};
+use alkanes::indexer::index_block;
+use alkanes::precompiled::{alkanes_std_auth_token_build, alkanes_std_owned_token_build};
+use alkanes::tests::helpers as alkane_helpers;
+use alkanes_support::cellpack::Cellpack;
+use alkanes_support::constants::AUTH_TOKEN_FACTORY_ID;
+use alkanes_support::envelope::RawEnvelope;
+use alkanes_support::id::AlkaneId;
+use anyhow::Result;
+use bitcoin::{Block, OutPoint, Witness};
+use protorune::test_helpers::{create_block_with_coinbase_tx, get_address, ADDRESS1};
+
+pub const START_HEIGHT: u32 = 840_000;
+pub const FUNDED_AMOUNT: u128 = 1_000_000;
+pub const SECOND_FUNDED_AMOUNT: u128 = 500_000;
+
+//Deploy order fixes the ids: two owned tokens each followed by their auth token, then the distributor
+pub const TOKEN_ID: AlkaneId = AlkaneId { block: 2, tx: 0 };
+pub const SECOND_TOKEN_ID: AlkaneId = AlkaneId { block: 2, tx: 2 };
+pub const DISTRIBUTOR_ID: AlkaneId = AlkaneId { block: 2, tx: 4 };
+
+//BIP-173 regtest vector, anything that isn't ADDRESS1 works
+pub const OTHER_ADDRESS: &str = "bcrt1qw508d6qejxtdg4y5r3zarvary0c5xw7kygt080";
+
+pub struct Airdrop {
+    pub leaves: Vec<Vec<u8>>,
+    pub root: Vec<u8>,
+}
+
+pub fn leaf(index: u32, address: String, amounts: &[(u32, u128)]) -> SchemaMerkleLeaf {
+    SchemaMerkleLeaf {
+        index,
+        address,
+        amounts: amounts
+            .iter()
+            .map(|(asset_index, amount)| SchemaLeafAmount {
+                asset_index: *asset_index,
+                amount: *amount,
+            })
+            .collect(),
+    }
+}
+
+impl Airdrop {
+    //Two leaf v2 tree: index 0 pays whoever owns vout #0 of the helper transactions, index 1 doesn't
+    pub fn new() -> Result<Self> {
+        Self::from_leaves(
+            leaf(0, vout0_address(), &[(0, 1_000)]),
+            leaf(1, OTHER_ADDRESS.to_string(), &[(0, 2_000)]),
+        )
+    }
+
+    pub fn from_leaves(first: SchemaMerkleLeaf, second: SchemaMerkleLeaf) -> Result<Self> {
+        let leaves = vec![borsh::to_vec(&first)?, borsh::to_vec(&second)?];
+
+        let a = tagged_hash(MERKLE_LEAF_TAG, &[&leaves[0]]);
+        let b = tagged_hash(MERKLE_LEAF_TAG, &[&leaves[1]]);
+        let (left, right) = if a <= b { (a, b) } else { (b, a) };
+        let root = tagged_hash(MERKLE_NODE_TAG, &[&left, &right]).to_vec();
+
+        Ok(Self { leaves, root })
+    }
+
+    pub fn proof(&self, index: usize) -> SchemaMerkleProof {
+        SchemaMerkleProof {
+            version: 2,
+            leaf: self.leaves[index].clone(),
+            proofs: vec![tagged_hash(MERKLE_LEAF_TAG, &[&self.leaves[index ^ 1]]).to_vec()],
+        }
+    }
+
+    //Both leaves prove each other, so the multiproof needs no siblings
+    pub fn multiproof(&self, recipient_vouts: Vec<u32>) -> SchemaMerkleMultiProof {
+        SchemaMerkleMultiProof {
+            version: 2,
+            leaf_count: 2,
+            indices: vec![0, 1],
+            leaves: self.leaves.clone(),
+            proofs: vec![],
+            recipient_vouts,
+        }
+    }
+}
+
+pub fn token_balance(block: &Block) -> Result<u128> {
+    alkane_balance(block, TOKEN_ID)
+}
+
+pub fn second_token_balance(block: &Block) -> Result<u128> {
+    alkane_balance(block, SECOND_TOKEN_ID)
+}
+
+//vout #0 of every helper transaction pays this address
+pub fn vout0_address() -> String {
+    get_address(&ADDRESS1()).to_string()
+}
+
+pub fn deposit(alkane_id: AlkaneId, amount: u128) -> SchemaDistributorDeposit {
+    SchemaDistributorDeposit {
+        alkane_id: SchemaAlkaneId {
+            block: alkane_id.block as u32,
+            tx: alkane_id.tx as u64,
+        },
+        amount,
+    }
+}
+
+pub fn distributor_params(
+    airdrop: &Airdrop,
+    block_end: u128,
+) -> SchemaInitializeMerkleDistributorParameters {
+    SchemaInitializeMerkleDistributorParameters {
+        merkle_root: airdrop.root.clone(),
+        deposits: vec![deposit(TOKEN_ID, FUNDED_AMOUNT)],
+        block_end,
+        allow_v1_proofs: false,
+        owner_address: Some(vout0_address()),
+    }
+}
+
+pub fn setup(airdrop: &Airdrop, block_end: u128) -> Result<()> {
+    setup_with(&distributor_params(airdrop, block_end))?;
+    Ok(())
+}
+
+//Mints FUNDED_AMOUNT of TOKEN_ID and SECOND_FUNDED_AMOUNT of SECOND_TOKEN_ID, each to vout #0 of
+//its own transaction, the block's last two
+pub fn mint_funding() -> Result<Block> {
+    alkane_helpers::clear();
+
+    let token_block = alkane_helpers::init_with_multiple_cellpacks_with_tx(
+        vec![
+            alkanes_std_auth_token_build::get_bytes(),
+            alkanes_std_owned_token_build::get_bytes(),
+            alkanes_std_owned_token_build::get_bytes(),
+        ],
+        vec![
+            Cellpack {
+                target: AlkaneId {
+                    block: 3,
+                    tx: AUTH_TOKEN_FACTORY_ID,
+                },
+                inputs: vec![100],
+            },
+            Cellpack {
+                target: AlkaneId { block: 1, tx: 0 },
+                inputs: vec![0, 1, FUNDED_AMOUNT],
+            },
+            Cellpack {
+                target: AlkaneId { block: 1, tx: 0 },
+                inputs: vec![0, 1, SECOND_FUNDED_AMOUNT],
+            },
+        ],
+    );
+    index_block(&token_block, START_HEIGHT)?;
+
+    Ok(token_block)
+}
+
+pub fn funding_outpoints(block: &Block) -> Vec<OutPoint> {
+    block.txdata[block.txdata.len() - 2..]
+        .iter()
+        .map(|tx| OutPoint {
+            txid: tx.compute_txid(),
+            vout: 0,
+        })
+        .collect()
+}
+
+//Deploys the distributor from a transaction that escrows all of the funding
+pub fn setup_with(params: &SchemaInitializeMerkleDistributorParameters) -> Result<Block> {
+    let token_block = mint_funding()?;
+
+    //Spends the outputs holding the freshly minted tokens so initialize can escrow them
+    index_tx(
+        build_tx(
+            funding_outpoints(&token_block),
+            RawEnvelope::from(merkle_distributor_build::get_bytes()).to_witness(true),
+            vec![address_output(&vout0_address())?],
+            vec![message(
+                Cellpack {
+                    target: AlkaneId { block: 1, tx: 0 },
+                    inputs: to_inputs(0, &borsh::to_vec(params)?),
+                },
+                0,
+            )],
+        )?,
+        START_HEIGHT + 1,
+    )
+}
+
+//Claims read their payload from the transaction's witness
+pub fn call_distributor(inputs: Vec<u128>, witness: Witness, height: u32) -> Result<Block> {
+    let mut block = create_block_with_coinbase_tx(height);
+    block
+        .txdata
+        .push(alkane_helpers::create_multiple_cellpack_with_witness(
+            witness,
+            vec![Cellpack {
+                target: DISTRIBUTOR_ID,
+                inputs,
+            }],
+            false,
+        ));
+    index_block(&block, height)?;
+    Ok(block)
+}
+
+pub fn claim(proof: &SchemaMerkleProof, height: u32) -> Result<Block> {
+    call_distributor(
+        vec![106],
+        RawEnvelope::from(borsh::to_vec(proof)?).to_witness(false),
+        height,
+    )
+}
+
+pub fn claim_status(proof: &SchemaMerkleProof, height: u32) -> Result<SchemaClaimStatus> {
+    let block = call_distributor(
+        vec![111],
+        RawEnvelope::from(borsh::to_vec(proof)?).to_witness(false),
+        height,
+    )?;
+    Ok(borsh::from_slice(&return_data(&block)?)?)
+}
+
+pub fn distribution_stats(height: u32) -> Result<SchemaDistributionStats> {
+    let block = call_distributor(vec![114], Witness::new(), height)?;
+    Ok(borsh::from_slice(&return_data(&block)?)?)
+}
+
+//Pays the claimed leaves through a split protostone: the claim points at protostone #1, whose
+//edicts move each (alkane, amount) to an output. Outputs are ADDRESS1 then OTHER_ADDRESS
+pub fn claim_multiple(
+    multiproof: &SchemaMerkleMultiProof,
+    edicts: &[(AlkaneId, u128, u32)],
+    height: u32,
+) -> Result<Block> {
+    let outputs = vec![
+        address_output(&vout0_address())?,
+        address_output(OTHER_ADDRESS)?,
+    ];
+    //Past the outputs and the OP_RETURN, protostone #1 is shadow vout outputs + 2
+    let split_vout = outputs.len() as u32 + 2;
+
+    index_tx(
+        build_tx(
+            vec![OutPoint::null()],
+            RawEnvelope::from(borsh::to_vec(multiproof)?).to_witness(false),
+            outputs,
+            vec![
+                message(
+                    Cellpack {
+                        target: DISTRIBUTOR_ID,
+                        inputs: vec![109],
+                    },
+                    split_vout,
+                ),
+                split(edicts),
+            ],
+        )?,
+        height,
+    )
+}
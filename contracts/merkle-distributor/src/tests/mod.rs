@@ -0,0 +1,11 @@
+pub mod std;
+
+mod claim;
+mod clawback;
+mod helpers;
+mod multi_asset;
+mod multiproof;
+#[path = "../../../build-support/test_helpers.rs"]
+mod shared;
+mod signed;
+mod stats;
@@ -0,0 +1,121 @@
+use crate::schemas::SchemaInitializeMerkleDistributorParameters;
+use crate::tests::helpers::{
+    call_distributor, claim, deposit, distribution_stats, distributor_params, leaf,
+    second_token_balance, setup_with, token_balance, vout0_address, Airdrop, FUNDED_AMOUNT,
+    OTHER_ADDRESS, SECOND_FUNDED_AMOUNT, SECOND_TOKEN_ID, START_HEIGHT, TOKEN_ID,
+};
+use anyhow::Result;
+use bitcoin::Witness;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+//Leaf 0 pays both assets, leaf 1 only the second
+fn airdrop() -> Result<Airdrop> {
+    Airdrop::from_leaves(
+        leaf(0, vout0_address(), &[(0, 1_000), (1, 300)]),
+        leaf(1, OTHER_ADDRESS.to_string(), &[(1, 2_000)]),
+    )
+}
+
+fn params(airdrop: &Airdrop) -> SchemaInitializeMerkleDistributorParameters {
+    let mut params = distributor_params(airdrop, (START_HEIGHT + 10) as u128);
+    params.deposits = vec![
+        deposit(TOKEN_ID, FUNDED_AMOUNT),
+        deposit(SECOND_TOKEN_ID, SECOND_FUNDED_AMOUNT),
+    ];
+    params
+}
+
+#[wasm_bindgen_test]
+fn test_claim_pays_every_asset_in_the_leaf() -> Result<()> {
+    let airdrop = airdrop()?;
+    setup_with(&params(&airdrop))?;
+
+    let block = claim(&airdrop.proof(0), START_HEIGHT + 2)?;
+    assert_eq!(token_balance(&block)?, 1_000);
+    assert_eq!(second_token_balance(&block)?, 300);
+
+    let stats = distribution_stats(START_HEIGHT + 3)?;
+    assert_eq!(stats.assets.len(), 2);
+    assert_eq!(stats.assets[0].claimed, 1_000);
+    assert_eq!(stats.assets[0].remaining, FUNDED_AMOUNT - 1_000);
+    assert_eq!(stats.assets[1].claimed, 300);
+    assert_eq!(stats.assets[1].remaining, SECOND_FUNDED_AMOUNT - 300);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_clawback_returns_each_asset_remainder() -> Result<()> {
+    let airdrop = airdrop()?;
+    setup_with(&params(&airdrop))?;
+
+    claim(&airdrop.proof(0), START_HEIGHT + 2)?;
+
+    let block = call_distributor(vec![112], Witness::new(), START_HEIGHT + 11)?;
+    assert_eq!(token_balance(&block)?, FUNDED_AMOUNT - 1_000);
+    assert_eq!(second_token_balance(&block)?, SECOND_FUNDED_AMOUNT - 300);
+
+    let stats = distribution_stats(START_HEIGHT + 12)?;
+    assert_eq!(stats.assets[0].clawed_back, FUNDED_AMOUNT - 1_000);
+    assert_eq!(stats.assets[1].clawed_back, SECOND_FUNDED_AMOUNT - 300);
+    assert!(stats.assets.iter().all(|asset| asset.remaining == 0));
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_deposit_below_what_was_sent_refunds_the_excess() -> Result<()> {
+    let airdrop = airdrop()?;
+    let mut params = params(&airdrop);
+    params.deposits[1].amount = 100_000;
+
+    let deploy_block = setup_with(&params)?;
+    assert_eq!(token_balance(&deploy_block)?, 0);
+    assert_eq!(
+        second_token_balance(&deploy_block)?,
+        SECOND_FUNDED_AMOUNT - 100_000
+    );
+
+    let stats = distribution_stats(START_HEIGHT + 2)?;
+    assert_eq!(stats.assets[1].deposited, 100_000);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_invalid_deposits_are_rejected() -> Result<()> {
+    let airdrop = airdrop()?;
+
+    let mut above_sent = params(&airdrop);
+    above_sent.deposits[1].amount = SECOND_FUNDED_AMOUNT + 1;
+
+    let mut repeated = params(&airdrop);
+    repeated.deposits = vec![deposit(TOKEN_ID, 1), deposit(TOKEN_ID, 1)];
+
+    let mut empty = params(&airdrop);
+    empty.deposits = vec![];
+
+    for params in [above_sent, repeated, empty] {
+        //Initialize fails and everything sent is refunded to vout #0
+        let deploy_block = setup_with(&params)?;
+        assert_eq!(token_balance(&deploy_block)?, FUNDED_AMOUNT);
+        assert_eq!(second_token_balance(&deploy_block)?, SECOND_FUNDED_AMOUNT);
+    }
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_leaf_with_unknown_asset_is_rejected() -> Result<()> {
+    let airdrop = Airdrop::from_leaves(
+        leaf(0, vout0_address(), &[(0, 1_000), (2, 5)]),
+        leaf(1, OTHER_ADDRESS.to_string(), &[(1, 2_000)]),
+    )?;
+    setup_with(&params(&airdrop))?;
+
+    let block = claim(&airdrop.proof(0), START_HEIGHT + 2)?;
+    assert_eq!(token_balance(&block)?, 0);
+    assert_eq!(second_token_balance(&block)?, 0);
+
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use crate::schemas::SchemaClaimStatus;
+use crate::tests::helpers::{
+    claim, claim_multiple, claim_status, distribution_stats, setup, Airdrop, START_HEIGHT, TOKEN_ID,
+};
+use crate::tests::shared::alkane_balance_at;
+use anyhow::Result;
+use bitcoin::Block;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+//Leaf 0 (1,000) belongs to vout #0 and leaf 1 (2,000) to vout #1 of claim_multiple's transaction
+fn balances(block: &Block) -> Result<(u128, u128)> {
+    Ok((
+        alkane_balance_at(block, 0, TOKEN_ID)?,
+        alkane_balance_at(block, 1, TOKEN_ID)?,
+    ))
+}
+
+#[wasm_bindgen_test]
+fn test_multiproof_pays_each_leaf_to_its_output() -> Result<()> {
+    let airdrop = Airdrop::new()?;
+    setup(&airdrop, (START_HEIGHT + 100) as u128)?;
+
+    let block = claim_multiple(
+        &airdrop.multiproof(vec![0, 1]),
+        &[(TOKEN_ID, 1_000, 0), (TOKEN_ID, 2_000, 1)],
+        START_HEIGHT + 2,
+    )?;
+    assert_eq!(balances(&block)?, (1_000, 2_000));
+
+    for index in 0..2 {
+        assert_eq!(
+            claim_status(&airdrop.proof(index), START_HEIGHT + 3 + index as u32)?,
+            SchemaClaimStatus::Claimed
+        );
+    }
+
+    let stats = distribution_stats(START_HEIGHT + 5)?;
+    assert_eq!(stats.claim_count, 2);
+    assert_eq!(stats.assets[0].claimed, 3_000);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_split_that_does_not_pay_each_leaf_is_rejected() -> Result<()> {
+    let airdrop = Airdrop::new()?;
+    setup(&airdrop, (START_HEIGHT + 100) as u128)?;
+
+    let bad_splits: [&[_]; 4] = [
+        //Wrong amount to an output
+        &[(TOKEN_ID, 1_000, 0), (TOKEN_ID, 1_500, 1)],
+        //Amounts swapped between the outputs
+        &[(TOKEN_ID, 2_000, 0), (TOKEN_ID, 1_000, 1)],
+        //A leaf left unpaid
+        &[(TOKEN_ID, 1_000, 0)],
+        //No split at all
+        &[],
+    ];
+
+    for (i, edicts) in bad_splits.into_iter().enumerate() {
+        let block = claim_multiple(
+            &airdrop.multiproof(vec![0, 1]),
+            edicts,
+            START_HEIGHT + 2 + i as u32,
+        )?;
+        assert_eq!(balances(&block)?, (0, 0));
+    }
+
+    //Nothing was marked claimed, so the batch still goes through with the right split
+    let block = claim_multiple(
+        &airdrop.multiproof(vec![0, 1]),
+        &[(TOKEN_ID, 1_000, 0), (TOKEN_ID, 2_000, 1)],
+        START_HEIGHT + 10,
+    )?;
+    assert_eq!(balances(&block)?, (1_000, 2_000));
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_recipient_outputs_must_carry_the_leaf_addresses() -> Result<()> {
+    let airdrop = Airdrop::new()?;
+    setup(&airdrop, (START_HEIGHT + 100) as u128)?;
+
+    let block = claim_multiple(
+        &airdrop.multiproof(vec![1, 0]),
+        &[(TOKEN_ID, 1_000, 1), (TOKEN_ID, 2_000, 0)],
+        START_HEIGHT + 2,
+    )?;
+    assert_eq!(balances(&block)?, (0, 0));
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_multiproof_with_a_claimed_leaf_is_rejected() -> Result<()> {
+    let airdrop = Airdrop::new()?;
+    setup(&airdrop, (START_HEIGHT + 100) as u128)?;
+
+    claim(&airdrop.proof(0), START_HEIGHT + 2)?;
+
+    let block = claim_multiple(
+        &airdrop.multiproof(vec![0, 1]),
+        &[(TOKEN_ID, 1_000, 0), (TOKEN_ID, 2_000, 1)],
+        START_HEIGHT + 3,
+    )?;
+    assert_eq!(balances(&block)?, (0, 0));
+
+    //Leaf 1 wasn't paid either
+    assert_eq!(
+        claim_status(&airdrop.proof(1), START_HEIGHT + 4)?,
+        SchemaClaimStatus::Unclaimed
+    );
+
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use crate::schemas::{SchemaClaimStatus, SchemaSignedMerkleClaim};
+use crate::tests::helpers::{
+    call_distributor, claim_status, leaf, setup, token_balance, vout0_address, Airdrop,
+    DISTRIBUTOR_ID, OTHER_ADDRESS, START_HEIGHT,
+};
+use crate::tests::shared::address_output;
+use crate::utils::calc_claim_intent_digest;
+use alkanes_support::envelope::RawEnvelope;
+use anyhow::Result;
+use bitcoin::secp256k1::{Message, Secp256k1, SecretKey};
+use bitcoin::{Address, Block, CompressedPublicKey, Network};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const SIGNER: u8 = 7;
+
+fn secret(byte: u8) -> SecretKey {
+    SecretKey::from_slice(&[byte; 32]).unwrap()
+}
+
+fn pubkey(byte: u8) -> CompressedPublicKey {
+    CompressedPublicKey(secret(byte).public_key(&Secp256k1::new()))
+}
+
+//Leaf 0 belongs to a p2wpkh address nobody spends from in the helper transactions
+fn airdrop() -> Result<Airdrop> {
+    Airdrop::from_leaves(
+        leaf(
+            0,
+            Address::p2wpkh(&pubkey(SIGNER), Network::Regtest).to_string(),
+            &[(0, 1_000)],
+        ),
+        leaf(1, OTHER_ADDRESS.to_string(), &[(0, 2_000)]),
+    )
+}
+
+//`signer` signs over `recipient`, the claim itself is always paid to vout #0
+fn signed_claim(airdrop: &Airdrop, signer: u8, recipient: &str) -> Result<SchemaSignedMerkleClaim> {
+    let proof = airdrop.proof(0);
+    let digest = calc_claim_intent_digest(
+        &DISTRIBUTOR_ID,
+        &proof.leaf,
+        address_output(recipient)?.script_pubkey.as_bytes(),
+    );
+    let signature = Secp256k1::new().sign_ecdsa(&Message::from_digest(digest), &secret(signer));
+
+    Ok(SchemaSignedMerkleClaim {
+        proof,
+        recipient_vout: 0,
+        pubkey: pubkey(signer).to_bytes().to_vec(),
+        signature: signature.serialize_compact().to_vec(),
+    })
+}
+
+fn claim_signed(signed_claim: &SchemaSignedMerkleClaim, height: u32) -> Result<Block> {
+    call_distributor(
+        vec![108],
+        RawEnvelope::from(borsh::to_vec(signed_claim)?).to_witness(false),
+        height,
+    )
+}
+
+#[wasm_bindgen_test]
+fn test_signed_claim_pays_the_signed_output() -> Result<()> {
+    let airdrop = airdrop()?;
+    setup(&airdrop, (START_HEIGHT + 100) as u128)?;
+
+    let signed = signed_claim(&airdrop, SIGNER, &vout0_address())?;
+    let block = claim_signed(&signed, START_HEIGHT + 2)?;
+    assert_eq!(token_balance(&block)?, 1_000);
+
+    assert_eq!(
+        claim_status(&airdrop.proof(0), START_HEIGHT + 3)?,
+        SchemaClaimStatus::Claimed
+    );
+
+    //The signature can't be replayed once the leaf is used
+    let replay = claim_signed(&signed, START_HEIGHT + 4)?;
+    assert_eq!(token_balance(&replay)?, 0);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_signature_over_another_output_is_rejected() -> Result<()> {
+    let airdrop = airdrop()?;
+    setup(&airdrop, (START_HEIGHT + 100) as u128)?;
+
+    let signed = signed_claim(&airdrop, SIGNER, OTHER_ADDRESS)?;
+    let block = claim_signed(&signed, START_HEIGHT + 2)?;
+    assert_eq!(token_balance(&block)?, 0);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_signature_by_another_key_is_rejected() -> Result<()> {
+    let airdrop = airdrop()?;
+    setup(&airdrop, (START_HEIGHT + 100) as u128)?;
+
+    let signed = signed_claim(&airdrop, SIGNER + 1, &vout0_address())?;
+    let block = claim_signed(&signed, START_HEIGHT + 2)?;
+    assert_eq!(token_balance(&block)?, 0);
+
+    //The right key with another key's signature doesn't pass either
+    let mut mixed = signed_claim(&airdrop, SIGNER, &vout0_address())?;
+    mixed.signature = signed.signature;
+    let block = claim_signed(&mixed, START_HEIGHT + 3)?;
+    assert_eq!(token_balance(&block)?, 0);
+
+    assert_eq!(
+        claim_status(&airdrop.proof(0), START_HEIGHT + 4)?,
+        SchemaClaimStatus::Unclaimed
+    );
+
+    Ok(())
+}
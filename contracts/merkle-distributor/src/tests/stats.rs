@@ -0,0 +1,98 @@
+use crate::tests::helpers::{
+    call_distributor, claim, claim_multiple, distribution_stats, leaf, setup, vout0_address,
+    Airdrop, FUNDED_AMOUNT, START_HEIGHT, TOKEN_ID,
+};
+use crate::tests::shared::return_data;
+use anyhow::{anyhow, Result};
+use bitcoin::Witness;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+//Both leaves belong to vout #0 so either claim mode can take them
+fn airdrop() -> Result<Airdrop> {
+    Airdrop::from_leaves(
+        leaf(0, vout0_address(), &[(0, 1_000)]),
+        leaf(1, vout0_address(), &[(0, 2_000)]),
+    )
+}
+
+fn claims_at_height(height: u32, queried_at: u32) -> Result<u128> {
+    let block = call_distributor(vec![115, height as u128], Witness::new(), queried_at)?;
+    Ok(u128::from_le_bytes(
+        return_data(&block)?.try_into().unwrap(),
+    ))
+}
+
+#[wasm_bindgen_test]
+fn test_stats_before_any_claim() -> Result<()> {
+    let airdrop = airdrop()?;
+    setup(&airdrop, (START_HEIGHT + 100) as u128)?;
+
+    let stats = distribution_stats(START_HEIGHT + 2)?;
+    assert_eq!(stats.claim_count, 0);
+    assert_eq!(stats.first_claim_height, 0);
+    assert_eq!(stats.last_claim_height, 0);
+    assert_eq!(stats.assets.len(), 1);
+    assert_eq!(stats.assets[0].deposited, FUNDED_AMOUNT);
+    assert_eq!(stats.assets[0].claimed, 0);
+    assert_eq!(stats.assets[0].remaining, FUNDED_AMOUNT);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_stats_track_claims_and_heights() -> Result<()> {
+    let airdrop = airdrop()?;
+    setup(&airdrop, (START_HEIGHT + 100) as u128)?;
+
+    claim(&airdrop.proof(0), START_HEIGHT + 2)?;
+    claim(&airdrop.proof(1), START_HEIGHT + 5)?;
+
+    let stats = distribution_stats(START_HEIGHT + 6)?;
+    assert_eq!(stats.claim_count, 2);
+    assert_eq!(stats.first_claim_height, (START_HEIGHT + 2) as u64);
+    assert_eq!(stats.last_claim_height, (START_HEIGHT + 5) as u64);
+    assert_eq!(stats.assets[0].claimed, 3_000);
+    assert_eq!(stats.assets[0].remaining, FUNDED_AMOUNT - 3_000);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_rejected_claims_are_not_counted() -> Result<()> {
+    let airdrop = airdrop()?;
+    setup(&airdrop, (START_HEIGHT + 100) as u128)?;
+
+    claim(&airdrop.proof(0), START_HEIGHT + 2)?;
+    //Already claimed
+    claim(&airdrop.proof(0), START_HEIGHT + 3)?;
+
+    let stats = distribution_stats(START_HEIGHT + 4)?;
+    assert_eq!(stats.claim_count, 1);
+    assert_eq!(stats.last_claim_height, (START_HEIGHT + 2) as u64);
+    assert_eq!(claims_at_height(START_HEIGHT + 3, START_HEIGHT + 5)?, 0);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_claims_at_height_counts_each_leaf() -> Result<()> {
+    let airdrop = airdrop()?;
+    setup(&airdrop, (START_HEIGHT + 100) as u128)?;
+
+    //One call paying both leaves counts twice
+    claim_multiple(
+        &airdrop.multiproof(vec![0, 0]),
+        &[(TOKEN_ID, 1_000, 0), (TOKEN_ID, 2_000, 0)],
+        START_HEIGHT + 4,
+    )?;
+
+    assert_eq!(claims_at_height(START_HEIGHT + 4, START_HEIGHT + 5)?, 2);
+    assert_eq!(claims_at_height(START_HEIGHT + 3, START_HEIGHT + 6)?, 0);
+
+    let stats = distribution_stats(START_HEIGHT + 7)?;
+    assert_eq!(stats.claim_count, 2);
+    assert_eq!(stats.first_claim_height, (START_HEIGHT + 4) as u64);
+    assert_eq!(stats.last_claim_height, (START_HEIGHT + 4) as u64);
+
+    Ok(())
+}
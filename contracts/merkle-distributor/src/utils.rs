@@ -1,13 +1,18 @@
 use crate::MerkleDistributor;
 use alkanes_runtime::runtime::AlkaneResponder;
 use alkanes_support::context::Context;
+use alkanes_support::id::AlkaneId;
 use alkanes_support::witness::find_witness_payload;
-use anyhow::{anyhow, Result};
-use bitcoin::Transaction;
+use anyhow::{anyhow, ensure, Context as _, Result};
+use bitcoin::{Address, Transaction};
+use merkle_support::hash_merkle_pair;
 use ordinals::{Artifact, Runestone};
 use protorune_support::{protostone::Protostone, utils::consensus_decode};
 use sha2::{Digest, Sha256};
 use std::io::Cursor;
+
+pub use merkle_support::{tagged_hash, MERKLE_LEAF_TAG, MERKLE_NODE_TAG};
+
 pub fn u128_to_string(v: u128) -> String {
     String::from_utf8(
         v.to_le_bytes()
@@ -45,29 +50,236 @@ pub fn extract_witness_payload(tx: &Transaction) -> Option<Vec<u8>> {
     None
 }
 
-pub fn calc_merkle_root(leaf: &[u8], proofs: &[Vec<u8>]) -> [u8; 32] {
-    let mut node: Vec<u8> = leaf.to_vec();
+//Single proofs are rebuilt in merkle_support, the same code tacoclicker's airdrop runs
+pub fn calc_merkle_root_for_version(
+    version: u8,
+    leaf: &[u8],
+    proofs: &[Vec<u8>],
+    allow_v1: bool,
+) -> Result<[u8; 32]> {
+    merkle_support::calc_merkle_root_for_version(version, leaf, proofs, allow_v1)
+        .map_err(|e| anyhow!("MERKLE DISTRIBUTOR: {e}"))
+}
 
-    for sib in proofs {
-        let (left, right) = if node <= *sib {
-            (&node, sib)
-        } else {
-            (sib, &node)
-        };
-        let mut hasher = Sha256::new();
-        hasher.update(left);
-        hasher.update(right);
-        node = hasher.finalize().to_vec();
+//Rebuilds the root layer by layer from several leaves at once. Siblings that are themselves
+//being proven are hashed together instead of supplied, and a node left without a sibling at
+//the end of a layer is carried up unchanged, matching how the trees are built
+pub fn calc_merkle_multiproof_root(
+    version: u8,
+    leaf_count: u32,
+    indices: &[u32],
+    leaves: &[Vec<u8>],
+    proofs: &[Vec<u8>],
+    allow_v1: bool,
+) -> Result<[u8; 32]> {
+    ensure!(
+        !leaves.is_empty() && leaves.len() == indices.len(),
+        "MERKLE DISTRIBUTOR: multiproof needs one index per leaf"
+    );
+    ensure!(
+        indices.windows(2).all(|w| w[0] < w[1]),
+        "MERKLE DISTRIBUTOR: multiproof indices must be strictly increasing"
+    );
+    ensure!(
+        indices[indices.len() - 1] < leaf_count,
+        "MERKLE DISTRIBUTOR: multiproof index out of range"
+    );
+
+    let mut known: Vec<(u32, Vec<u8>)> = match version {
+        1 => {
+            ensure!(
+                allow_v1,
+                "MERKLE DISTRIBUTOR: v1 merkle proofs are not accepted"
+            );
+            indices
+                .iter()
+                .copied()
+                .zip(leaves.iter().cloned())
+                .collect()
+        }
+        2 => indices
+            .iter()
+            .zip(leaves)
+            .map(|(index, leaf)| (*index, tagged_hash(MERKLE_LEAF_TAG, &[leaf]).to_vec()))
+            .collect(),
+        _ => {
+            return Err(anyhow!(
+                "MERKLE DISTRIBUTOR: unknown merkle proof version {version}"
+            ))
+        }
+    };
+
+    let mut siblings = proofs.iter();
+    let mut width = leaf_count;
+
+    while width > 1 {
+        let mut next: Vec<(u32, Vec<u8>)> = Vec::with_capacity(known.len());
+        let mut i = 0;
+
+        while i < known.len() {
+            let (position, node) = &known[i];
+            let sibling = position ^ 1;
+
+            let parent = if i + 1 < known.len() && known[i + 1].0 == sibling {
+                i += 1;
+                hash_merkle_pair(version, node, &known[i].1)
+            } else if sibling < width {
+                let sib = siblings
+                    .next()
+                    .context("MERKLE DISTRIBUTOR: multiproof is missing siblings")?;
+                ensure!(
+                    version == 1 || sib.len() == 32,
+                    "MERKLE DISTRIBUTOR: v2 merkle proof siblings must be 32 bytes"
+                );
+                hash_merkle_pair(version, node, sib)
+            } else {
+                node.clone()
+            };
+
+            next.push((position / 2, parent));
+            i += 1;
+        }
+
+        known = next;
+        width = width.div_ceil(2);
     }
 
-    // convert Vec<u8> → [u8;32]
+    ensure!(
+        siblings.next().is_none(),
+        "MERKLE DISTRIBUTOR: multiproof has unused siblings"
+    );
+
+    let node = &known[0].1;
+    ensure!(
+        node.len() == 32,
+        "MERKLE DISTRIBUTOR: multiproof does not reduce to a 32 byte root"
+    );
+
     let mut root = [0u8; 32];
-    root.copy_from_slice(&node);
-    root
+    root.copy_from_slice(node);
+    Ok(root)
+}
+
+//What the leaf owner signs to let someone else's output receive their claim
+pub fn calc_claim_intent_digest(
+    contract: &AlkaneId,
+    leaf: &[u8],
+    recipient_script: &[u8],
+) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(b"MERKLE DISTRIBUTOR/claim");
+    hasher.update(contract.block.to_le_bytes());
+    hasher.update(contract.tx.to_le_bytes());
+    hasher.update(Sha256::digest(leaf));
+    hasher.update(recipient_script);
+    hasher.finalize().into()
+}
+
+//Verified in merkle_support, shared with tacoclicker's airdrop
+pub fn verify_claim_signature(
+    leaf_address: &Address,
+    digest: [u8; 32],
+    pubkey: &[u8],
+    signature: &[u8],
+) -> Result<()> {
+    merkle_support::verify_claim_signature(leaf_address, digest, pubkey, signature)
+        .map_err(|e| anyhow!("MERKLE DISTRIBUTOR: {e}"))
 }
 
 impl MerkleDistributor {
+    //A call can only send alkanes to its pointer, so a batch claim points at a later protostone
+    //whose edicts split the payout. Each (alkane, vout, amount) must be paid exactly, with nothing left over
+    pub fn validate_protostone_split(
+        &self,
+        ctx: &Context,
+        payouts: &[(AlkaneId, u32, u128)],
+    ) -> Result<()> {
+        let tx = consensus_decode::<Transaction>(&mut Cursor::new(self.transaction()))
+            .map_err(|_| anyhow!("failed to decode transaction bytes"))?;
+
+        let runestone = match Runestone::decipher(&tx) {
+            Some(Artifact::Runestone(r)) => r,
+            _ => return Err(anyhow!("transaction does not contain a runestone")),
+        };
+
+        let protostones = Protostone::from_runestone(&runestone)
+            .map_err(|e| anyhow!("failed to parse protostone: {e}"))?;
+
+        let shadow_base = tx.output.len() as u32 + 1;
+
+        let pm_index =
+            ctx.vout
+                .checked_sub(shadow_base)
+                .ok_or_else(|| anyhow!("vout is not a protomessage index"))? as usize;
+
+        let message = protostones
+            .get(pm_index)
+            .ok_or_else(|| anyhow!("no protostone message at computed index"))?;
+
+        if !message.edicts.is_empty() {
+            return Err(anyhow!("protostone message must have zero edicts"));
+        }
+
+        let pointer = message
+            .pointer
+            .ok_or_else(|| anyhow!("protostone message has no pointer"))?;
+
+        let split_index = pointer
+            .checked_sub(shadow_base)
+            .ok_or_else(|| anyhow!("pointer must target the protostone that splits the claim"))?
+            as usize;
+
+        ensure!(
+            split_index > pm_index,
+            "split protostone must come after the claim"
+        );
+
+        let split = protostones
+            .get(split_index)
+            .ok_or_else(|| anyhow!("no protostone at pointer {pointer}"))?;
+
+        let mut remaining: Vec<(AlkaneId, u32, u128)> = payouts.to_vec();
+
+        for edict in split.edicts.iter().filter(|e| {
+            payouts
+                .iter()
+                .any(|(alkane, _, _)| e.id.block == alkane.block && e.id.tx == alkane.tx)
+        }) {
+            let position = remaining
+                .iter()
+                .position(|(alkane, vout, amount)| {
+                    edict.id.block == alkane.block
+                        && edict.id.tx == alkane.tx
+                        && edict.output == *vout as u128
+                        && edict.amount == *amount
+                })
+                .ok_or_else(|| {
+                    anyhow!(
+                        "split edict of {} to output {} does not match a claimed leaf",
+                        edict.amount,
+                        edict.output
+                    )
+                })?;
+            remaining.swap_remove(position);
+        }
+
+        ensure!(
+            remaining.is_empty(),
+            "split protostone does not pay every claimed leaf"
+        );
+
+        Ok(())
+    }
+
     pub fn validate_protostone_tx(&self, ctx: &Context) -> Result<()> {
+        self.validate_protostone_tx_pointer(ctx, 0)
+    }
+
+    pub fn validate_protostone_tx_pointer(
+        &self,
+        ctx: &Context,
+        expected_pointer: u32,
+    ) -> Result<()> {
         let tx = consensus_decode::<Transaction>(&mut Cursor::new(self.transaction()))
             .map_err(|_| anyhow!("failed to decode transaction bytes"))?;
 
@@ -102,8 +314,10 @@ impl MerkleDistributor {
             ));
         }
 
-        if pointer != 0 {
-            return Err(anyhow!("pointer must be set to 0! found {pointer}"));
+        if pointer != expected_pointer {
+            return Err(anyhow!(
+                "pointer must be set to {expected_pointer}! found {pointer}"
+            ));
         }
 
         Ok(())
@@ -0,0 +1,13 @@
+//Shared by tacoclicker's airdrop, the merkle distributor and the merkle-tree tool so the checks
+//can't drift between them. Errors carry no prefix, callers add their own.
+pub mod merkle;
+pub mod signature;
+
+#[cfg(test)]
+mod tests;
+
+pub use merkle::{
+    calc_merkle_root, calc_merkle_root_for_version, calc_merkle_root_v2, hash_merkle_pair,
+    tagged_hash, MERKLE_LEAF_TAG, MERKLE_NODE_TAG,
+};
+pub use signature::verify_claim_signature;
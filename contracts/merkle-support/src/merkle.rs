@@ -0,0 +1,76 @@
+use anyhow::{anyhow, ensure, Result};
+use sha2::{Digest, Sha256};
+
+//v1, the original untagged format. Pairs are ordered before hashing so proofs need no direction bit
+pub fn calc_merkle_root(leaf: &[u8], proofs: &[Vec<u8>]) -> [u8; 32] {
+    let mut node: Vec<u8> = leaf.to_vec();
+
+    for sib in proofs {
+        node = hash_merkle_pair(1, &node, sib);
+    }
+
+    // convert Vec<u8> → [u8;32]
+    let mut root = [0u8; 32];
+    root.copy_from_slice(&node);
+    root
+}
+
+//v2 trees domain-separate leaves from inner nodes so an inner node can never be passed off as a leaf
+pub const MERKLE_LEAF_TAG: &[u8] = b"TacoClickerMerkleLeaf";
+pub const MERKLE_NODE_TAG: &[u8] = b"TacoClickerMerkleNode";
+
+pub fn tagged_hash(tag: &[u8], parts: &[&[u8]]) -> [u8; 32] {
+    let tag_hash = Sha256::digest(tag);
+
+    let mut hasher = Sha256::new();
+    hasher.update(tag_hash);
+    hasher.update(tag_hash);
+    for part in parts {
+        hasher.update(part);
+    }
+    hasher.finalize().into()
+}
+
+pub fn hash_merkle_pair(version: u8, a: &[u8], b: &[u8]) -> Vec<u8> {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+
+    match version {
+        1 => {
+            let mut hasher = Sha256::new();
+            hasher.update(left);
+            hasher.update(right);
+            hasher.finalize().to_vec()
+        }
+        _ => tagged_hash(MERKLE_NODE_TAG, &[left, right]).to_vec(),
+    }
+}
+
+pub fn calc_merkle_root_v2(leaf: &[u8], proofs: &[Vec<u8>]) -> Result<[u8; 32]> {
+    let mut node = tagged_hash(MERKLE_LEAF_TAG, &[leaf]);
+
+    for sib in proofs {
+        ensure!(sib.len() == 32, "v2 merkle proof siblings must be 32 bytes");
+
+        let parent = hash_merkle_pair(2, &node, sib);
+        node.copy_from_slice(&parent);
+    }
+
+    Ok(node)
+}
+
+//v1 is only accepted where the root was built that way
+pub fn calc_merkle_root_for_version(
+    version: u8,
+    leaf: &[u8],
+    proofs: &[Vec<u8>],
+    allow_v1: bool,
+) -> Result<[u8; 32]> {
+    match version {
+        1 => {
+            ensure!(allow_v1, "v1 merkle proofs are not accepted");
+            Ok(calc_merkle_root(leaf, proofs))
+        }
+        2 => calc_merkle_root_v2(leaf, proofs),
+        _ => Err(anyhow!("unknown merkle proof version {version}")),
+    }
+}
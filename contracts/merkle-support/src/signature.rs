@@ -0,0 +1,49 @@
+use anyhow::{anyhow, ensure, Context, Result};
+use bitcoin::secp256k1::{ecdsa, schnorr, Message, Secp256k1, XOnlyPublicKey};
+use bitcoin::{Address, CompressedPublicKey, ScriptBuf};
+
+//Taproot leaves sign with BIP-340 schnorr under the output key, segwit v0 leaves sign compact ECDSA
+pub fn verify_claim_signature(
+    leaf_address: &Address,
+    digest: [u8; 32],
+    pubkey: &[u8],
+    signature: &[u8],
+) -> Result<()> {
+    let secp = Secp256k1::verification_only();
+    let message = Message::from_digest(digest);
+    let leaf_script = leaf_address.script_pubkey();
+
+    if leaf_script.is_p2tr() {
+        let output_key = XOnlyPublicKey::from_slice(&leaf_script.as_bytes()[2..34])
+            .context("invalid taproot output key in leaf address")?;
+        let signature =
+            schnorr::Signature::from_slice(signature).context("invalid schnorr signature")?;
+
+        secp.verify_schnorr(&signature, &message, &output_key)
+            .map_err(|_| anyhow!("claim signature does not match leaf address"))?;
+
+        return Ok(());
+    }
+
+    if leaf_script.is_p2wpkh() {
+        let pubkey =
+            CompressedPublicKey::from_slice(pubkey).context("invalid compressed public key")?;
+
+        ensure!(
+            ScriptBuf::new_p2wpkh(&pubkey.wpubkey_hash()) == leaf_script,
+            "public key does not belong to leaf address"
+        );
+
+        let signature =
+            ecdsa::Signature::from_compact(signature).context("invalid ecdsa signature")?;
+
+        secp.verify_ecdsa(&message, &signature, &pubkey.0)
+            .map_err(|_| anyhow!("claim signature does not match leaf address"))?;
+
+        return Ok(());
+    }
+
+    Err(anyhow!(
+        "signed claims only support p2tr and p2wpkh leaf addresses"
+    ))
+}
@@ -0,0 +1,74 @@
+use crate::merkle::{
+    calc_merkle_root, calc_merkle_root_for_version, calc_merkle_root_v2, hash_merkle_pair,
+    tagged_hash, MERKLE_LEAF_TAG,
+};
+
+fn leaf(i: u8) -> Vec<u8> {
+    vec![i; 40]
+}
+
+//Two leaf v2 tree, returns (root, sibling of leaf 0)
+fn v2_pair() -> ([u8; 32], Vec<u8>) {
+    let a = tagged_hash(MERKLE_LEAF_TAG, &[&leaf(0)]);
+    let b = tagged_hash(MERKLE_LEAF_TAG, &[&leaf(1)]);
+
+    let mut root = [0u8; 32];
+    root.copy_from_slice(&hash_merkle_pair(2, &a, &b));
+    (root, b.to_vec())
+}
+
+#[test]
+fn test_pair_hash_ignores_order() {
+    for version in [1u8, 2] {
+        assert_eq!(
+            hash_merkle_pair(version, &leaf(0), &leaf(1)),
+            hash_merkle_pair(version, &leaf(1), &leaf(0))
+        );
+    }
+}
+
+#[test]
+fn test_v1_and_v2_roots_differ() {
+    let proofs = vec![vec![7u8; 32]];
+    assert_ne!(
+        calc_merkle_root(&leaf(0), &proofs),
+        calc_merkle_root_v2(&leaf(0), &proofs).unwrap()
+    );
+}
+
+#[test]
+fn test_v2_proof_rebuilds_root() {
+    let (root, sib) = v2_pair();
+    assert_eq!(calc_merkle_root_v2(&leaf(0), &[sib]).unwrap(), root);
+}
+
+#[test]
+fn test_v2_inner_node_is_not_a_leaf() {
+    let a = tagged_hash(MERKLE_LEAF_TAG, &[&leaf(0)]);
+    let b = tagged_hash(MERKLE_LEAF_TAG, &[&leaf(1)]);
+    let (root, _) = v2_pair();
+
+    //Presenting the concatenated children as a leaf must not land on the parent
+    let forged = [a, b].concat();
+    assert_ne!(calc_merkle_root_v2(&forged, &[]).unwrap(), root);
+}
+
+#[test]
+fn test_v2_rejects_short_siblings() {
+    assert!(calc_merkle_root_v2(&leaf(0), &[vec![0u8; 31]]).is_err());
+}
+
+#[test]
+fn test_v1_needs_allow_v1() {
+    let proofs = vec![vec![7u8; 32]];
+    assert!(calc_merkle_root_for_version(1, &leaf(0), &proofs, false).is_err());
+    assert_eq!(
+        calc_merkle_root_for_version(1, &leaf(0), &proofs, true).unwrap(),
+        calc_merkle_root(&leaf(0), &proofs)
+    );
+}
+
+#[test]
+fn test_unknown_version_is_rejected() {
+    assert!(calc_merkle_root_for_version(3, &leaf(0), &[], true).is_err());
+}
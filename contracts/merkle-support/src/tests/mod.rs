@@ -0,0 +1,2 @@
+mod merkle;
+mod signature;
@@ -0,0 +1,83 @@
+use crate::signature::verify_claim_signature;
+use bitcoin::key::{Keypair, TapTweak};
+use bitcoin::secp256k1::{Message, Secp256k1, SecretKey};
+use bitcoin::{Address, CompressedPublicKey, Network, PublicKey};
+
+const DIGEST: [u8; 32] = [42u8; 32];
+
+fn secret(byte: u8) -> SecretKey {
+    SecretKey::from_slice(&[byte; 32]).unwrap()
+}
+
+//Returns the leaf address and a signature over digest by its owner
+fn sign_p2tr(byte: u8, digest: [u8; 32]) -> (Address, Vec<u8>) {
+    let secp = Secp256k1::new();
+    let keypair = Keypair::from_secret_key(&secp, &secret(byte));
+    let (internal_key, _) = keypair.x_only_public_key();
+    let address = Address::p2tr(&secp, internal_key, None, Network::Regtest);
+
+    //p2tr outputs commit to the tweaked key, so that is what the owner signs with
+    let tweaked = keypair.tap_tweak(&secp, None).to_keypair();
+    let signature = secp.sign_schnorr_no_aux_rand(&Message::from_digest(digest), &tweaked);
+
+    (address, signature.as_ref().to_vec())
+}
+
+fn sign_p2wpkh(byte: u8, digest: [u8; 32]) -> (Address, Vec<u8>, Vec<u8>) {
+    let secp = Secp256k1::new();
+    let pubkey = CompressedPublicKey(secret(byte).public_key(&secp));
+    let address = Address::p2wpkh(&pubkey, Network::Regtest);
+    let signature = secp.sign_ecdsa(&Message::from_digest(digest), &secret(byte));
+
+    (
+        address,
+        pubkey.to_bytes().to_vec(),
+        signature.serialize_compact().to_vec(),
+    )
+}
+
+#[test]
+fn test_p2tr_signature_verifies() {
+    let (address, signature) = sign_p2tr(1, DIGEST);
+    verify_claim_signature(&address, DIGEST, &[], &signature).unwrap();
+}
+
+#[test]
+fn test_p2tr_signature_over_other_digest_fails() {
+    let (address, signature) = sign_p2tr(1, [7u8; 32]);
+    assert!(verify_claim_signature(&address, DIGEST, &[], &signature).is_err());
+}
+
+#[test]
+fn test_p2tr_signature_from_other_key_fails() {
+    let (address, _) = sign_p2tr(1, DIGEST);
+    let (_, signature) = sign_p2tr(2, DIGEST);
+    assert!(verify_claim_signature(&address, DIGEST, &[], &signature).is_err());
+}
+
+#[test]
+fn test_p2wpkh_signature_verifies() {
+    let (address, pubkey, signature) = sign_p2wpkh(1, DIGEST);
+    verify_claim_signature(&address, DIGEST, &pubkey, &signature).unwrap();
+}
+
+#[test]
+fn test_p2wpkh_pubkey_must_match_address() {
+    let (address, _, _) = sign_p2wpkh(1, DIGEST);
+    let (_, pubkey, signature) = sign_p2wpkh(2, DIGEST);
+    assert!(verify_claim_signature(&address, DIGEST, &pubkey, &signature).is_err());
+}
+
+#[test]
+fn test_p2wpkh_signature_over_other_digest_fails() {
+    let (address, pubkey, signature) = sign_p2wpkh(1, [7u8; 32]);
+    assert!(verify_claim_signature(&address, DIGEST, &pubkey, &signature).is_err());
+}
+
+#[test]
+fn test_legacy_addresses_are_rejected() {
+    let secp = Secp256k1::new();
+    let pubkey = PublicKey::new(secret(1).public_key(&secp));
+    let address = Address::p2pkh(pubkey, Network::Regtest);
+    assert!(verify_claim_signature(&address, DIGEST, &[], &[0u8; 64]).is_err());
+}
@@ -1,46 +1,241 @@
 use crate::utils::encoders::get_byte_array_from_inputs;
 use crate::{
     airdrop::{
-        schemas::{SchemaMerkleLeaf, SchemaMerkleProof},
-        utils::{calc_merkle_root, extract_witness_payload},
+        schemas::{
+            SchemaAddAirdropRoundParameters, SchemaAirdropClaimCheckParameters,
+            SchemaAirdropLeafParameters, SchemaAirdropRound, SchemaAirdropRoundParameters,
+            SchemaAirdropStatsView, SchemaAirdropVestingView, SchemaMerkleLeaf, SchemaMerkleProof,
+            SchemaSignedMerkleClaim, SchemaVestingPosition,
+        },
+        utils::{
+            calc_claim_intent_digest, calc_merkle_root_for_version, calc_vested_amount,
+            extract_witness_payload, verify_claim_signature,
+        },
     },
-    consts::{get_merkle_root_from_id, DEPLOYMENT_NETWORK},
+    consts::{DEPLOYMENT_NETWORK, TORTILLA_AIRDROP_PREMINE, TORTILLA_CLAIM_WINDOW},
+    treasury::schemas::{TreasuryInflowSource, TreasuryPolicy},
     utils::encoders::{decode_from_ctx, decode_from_vec},
     Tortilla,
 };
 use alkanes_runtime::{runtime::AlkaneResponder, storage::StoragePointer};
 use alkanes_support::{parcel::AlkaneTransfer, response::CallResponse};
 use anyhow::{anyhow, ensure, Context, Result};
-use bitcoin::Address;
+use bitcoin::{Address, Transaction};
 use borsh::BorshDeserialize;
 use metashrew_support::index_pointer::KeyValuePointer;
+use std::str::FromStr;
+use std::sync::Arc;
 
 //STORAGE GETTERS
 impl Tortilla {
-    fn get_used_leaf_pointer(&self, leaf_bytes: &Vec<u8>) -> StoragePointer {
-        StoragePointer::from_keyword("/used").select(leaf_bytes)
+    //Every round gets its own claimed-leaf namespace so the same leaf can show up in multiple rounds
+    fn get_used_leaf_pointer(&self, round: u32, leaf_bytes: &Vec<u8>) -> StoragePointer {
+        StoragePointer::from_keyword("/used")
+            .select(&round.to_le_bytes().to_vec())
+            .select(leaf_bytes)
     }
 
-    fn get_merkle_root(&self) -> Result<[u8; 32]> {
-        let consts = self.get_consts_value()?;
+    fn get_vesting_pointer(&self, round: u32, leaf_bytes: &Vec<u8>) -> StoragePointer {
+        StoragePointer::from_keyword("/vesting")
+            .select(&round.to_le_bytes().to_vec())
+            .select(leaf_bytes)
+    }
+
+    fn get_airdrop_round_count_pointer(&self) -> StoragePointer {
+        StoragePointer::from_keyword("/airdrop_round_count")
+    }
+
+    fn get_airdrop_round_pointer(&self, round: u32) -> StoragePointer {
+        StoragePointer::from_keyword("/airdrop_rounds").select(&round.to_le_bytes().to_vec())
+    }
+
+    fn get_airdrop_round(&self, round: u32) -> Result<SchemaAirdropRound> {
+        let bytes = (*self.get_airdrop_round_pointer(round).get()).clone();
+
+        ensure!(
+            !bytes.is_empty(),
+            "TORTILLA: airdrop round {round} not found"
+        );
+
+        decode_from_vec!(bytes, SchemaAirdropRound)
+    }
+
+    fn set_airdrop_round(&self, round: u32, airdrop_round: &SchemaAirdropRound) -> Result<()> {
+        self.get_airdrop_round_pointer(round).set(Arc::new(
+            borsh::to_vec(airdrop_round).context("TORTILLA: failed to encode airdrop round")?,
+        ));
+
+        Ok(())
+    }
+
+    fn push_airdrop_round(&self, airdrop_round: &SchemaAirdropRound) -> Result<u32> {
+        let mut ptr_round_count = self.get_airdrop_round_count_pointer();
+        let round = ptr_round_count.get_value::<u32>();
+
+        self.set_airdrop_round(round, airdrop_round)?;
+        ptr_round_count.set_value::<u32>(
+            round
+                .checked_add(1)
+                .context("TORTILLA: airdrop round count overflow")?,
+        );
+
+        Ok(round)
+    }
+
+    //Round 0 is the launch airdrop, using the (v1) merkle root compiled into the contract
+    pub fn create_initial_airdrop_round(&self, merkle_root: [u8; 32]) -> Result<()> {
+        self.push_airdrop_round(&SchemaAirdropRound {
+            merkle_root: merkle_root.to_vec(),
+            total: TORTILLA_AIRDROP_PREMINE,
+            claimed: 0u128,
+            swept: 0u128,
+            start_height: self.height(),
+            end_height: self.height() + TORTILLA_CLAIM_WINDOW,
+            allow_v1_proofs: true,
+        })?;
+
+        Ok(())
+    }
+
+    fn get_vesting_position(
+        &self,
+        round: u32,
+        leaf_bytes: &Vec<u8>,
+    ) -> Result<SchemaVestingPosition> {
+        let bytes = (*self.get_vesting_pointer(round, leaf_bytes).get()).clone();
+
+        ensure!(
+            !bytes.is_empty(),
+            "TORTILLA: no vesting position for this leaf, claim the airdrop first"
+        );
 
-        get_merkle_root_from_id(consts.merkle_root_id)
+        decode_from_vec!(bytes, SchemaVestingPosition)
     }
 
-    fn get_airdrop_end_height(&self) -> Result<u64> {
+    fn assert_vout0_address(&self, tx: &Transaction, address: &str) -> Result<()> {
+        let caller_script_pub_key = tx
+            .tx_out(0)
+            .context("MERKLE DISTRIBUTOR: vout #0 not present")?
+            .clone()
+            .script_pubkey;
+
+        let tx_address = Address::from_script(&caller_script_pub_key, DEPLOYMENT_NETWORK)?;
+
+        ensure!(
+            tx_address.to_string() == address,
+            "MERKLE DISTRIBUTOR: vout #0 doesnt contain the address in merkle proof"
+        );
+
+        Ok(())
+    }
+
+    //Mints whatever has vested but not been claimed yet and sends it out with the response
+    fn release_vested(
+        &self,
+        round: u32,
+        leaf_bytes: &Vec<u8>,
+        position: &mut SchemaVestingPosition,
+        response: &mut CallResponse,
+    ) -> Result<u128> {
         let consts = self.get_consts_value()?;
 
-        Ok(consts.airdrop_end_height)
+        let vested = calc_vested_amount(
+            position.total,
+            position.start_height,
+            &consts.airdrop_vesting,
+            self.height(),
+        );
+        let releasable = vested.saturating_sub(position.claimed);
+
+        position.claimed = position
+            .claimed
+            .checked_add(releasable)
+            .context("TORTILLA: overflow updating claimed vesting amount")?;
+
+        self.get_vesting_pointer(round, leaf_bytes)
+            .set(Arc::new(borsh::to_vec(position)?));
+
+        if releasable > 0 {
+            self.controlled_mint_contract_mint_new(
+                response,
+                consts.tortilla_alkane_id.into(),
+                releasable,
+            )?;
+
+            response.alkanes.0.push(AlkaneTransfer {
+                id: consts.tortilla_alkane_id.into(),
+                value: releasable,
+            });
+        }
+
+        Ok(releasable)
     }
 
-    fn validate_proof(&self, proof: &SchemaMerkleProof) -> Result<()> {
-        let merkle_root = self.get_merkle_root()?;
-        let airdrop_end_height = self.get_airdrop_end_height()?;
+    //Shared by both claim modes once the caller has proven they can act for the leaf
+    fn claim_airdrop_leaf(
+        &self,
+        round: u32,
+        merkle_proof: &SchemaMerkleProof,
+        recipient_address: String,
+        response: &mut CallResponse,
+    ) -> Result<()> {
+        let mut airdrop_round = self.get_airdrop_round(round)?;
 
-        let root_from_proof = calc_merkle_root(&proof.leaf, &proof.proofs);
+        self.validate_proof(&airdrop_round, merkle_proof)?;
 
-        let still_in_window = self.height() <= airdrop_end_height;
-        let root_matches = merkle_root == root_from_proof;
+        let mut ptr_used_leaf = self.get_used_leaf_pointer(round, &merkle_proof.leaf);
+        let used_leaf_check = ptr_used_leaf.get_value::<u8>();
+
+        ensure!(
+            used_leaf_check == 0u8,
+            "MERKLE DISTRIBUTOR: This leaf has already been used to claim"
+        );
+
+        let leaf = decode_from_vec!(merkle_proof.leaf, SchemaMerkleLeaf)?;
+
+        ptr_used_leaf.set_value(1u8);
+
+        airdrop_round.claimed = airdrop_round
+            .claimed
+            .checked_add(leaf.amount)
+            .context("TORTILLA: overflow tracking claimed airdrop")?;
+
+        ensure!(
+            airdrop_round.claimed <= airdrop_round.total,
+            "TORTILLA: airdrop claims exceed the declared airdrop total"
+        );
+
+        self.set_airdrop_round(round, &airdrop_round)?;
+
+        //The whole leaf amount goes into a vesting position, only the unlocked part is minted now
+        let mut position = SchemaVestingPosition {
+            address: recipient_address,
+            total: leaf.amount,
+            claimed: 0u128,
+            start_height: self.height(),
+        };
+
+        self.release_vested(round, &merkle_proof.leaf, &mut position, response)?;
+
+        Ok(())
+    }
+
+    fn validate_proof(
+        &self,
+        airdrop_round: &SchemaAirdropRound,
+        proof: &SchemaMerkleProof,
+    ) -> Result<()> {
+        let root_from_proof = calc_merkle_root_for_version(
+            proof.version,
+            &proof.leaf,
+            &proof.proofs,
+            airdrop_round.allow_v1_proofs,
+        )?;
+
+        let height = self.height();
+        let still_in_window =
+            height >= airdrop_round.start_height && height <= airdrop_round.end_height;
+        let root_matches = airdrop_round.merkle_root == root_from_proof;
 
         ensure!(
             root_matches && still_in_window,
@@ -52,20 +247,58 @@ impl Tortilla {
 }
 
 impl Tortilla {
+    pub fn add_airdrop_round(&self) -> Result<CallResponse> {
+        let ctx = self.context()?;
+        let mut response = CallResponse::forward(&ctx.incoming_alkanes);
+
+        self.assert_admin(&response)?;
+
+        let params = decode_from_ctx!(ctx, SchemaAddAirdropRoundParameters)?;
+
+        ensure!(
+            params.merkle_root.len() == 32,
+            "TORTILLA: merkle root must be 32 bytes"
+        );
+        ensure!(
+            params.start_height < params.end_height && params.end_height > self.height(),
+            "TORTILLA: airdrop round must end after it starts and after the current block"
+        );
+        //Sweeping or claiming a whole round has to fit in TORTILLA's per block mint limit
+        ensure!(
+            params.total <= TORTILLA_AIRDROP_PREMINE,
+            "TORTILLA: airdrop round can't be larger than {TORTILLA_AIRDROP_PREMINE}"
+        );
+
+        let round = self.push_airdrop_round(&SchemaAirdropRound {
+            merkle_root: params.merkle_root,
+            total: params.total,
+            claimed: 0u128,
+            swept: 0u128,
+            start_height: params.start_height,
+            end_height: params.end_height,
+            allow_v1_proofs: params.allow_v1_proofs,
+        })?;
+
+        response.data = borsh::to_vec(&round)?;
+
+        Ok(response)
+    }
+
     pub fn get_is_valid_airdrop_claim(&self) -> Result<CallResponse> {
         let ctx = self.context()?;
         let mut resp = CallResponse::forward(&ctx.incoming_alkanes);
 
         // decode the caller‑supplied proof (Borsh‑encoded in `ctx`)
-        let merkle_proof = decode_from_ctx!(&ctx, SchemaMerkleProof)?;
-        let ptr_used_leaf = self.get_used_leaf_pointer(&merkle_proof.leaf);
+        let params = decode_from_ctx!(&ctx, SchemaAirdropClaimCheckParameters)?;
+        let airdrop_round = self.get_airdrop_round(params.round)?;
+        let ptr_used_leaf = self.get_used_leaf_pointer(params.round, &params.proof.leaf);
         let used_leaf_check = ptr_used_leaf.get_value::<u8>();
 
         ensure!(
             used_leaf_check == 0u8,
             "MERKLE DISTRIBUTOR: This leaf has already been used to claim"
         );
-        self.validate_proof(&merkle_proof)?;
+        self.validate_proof(&airdrop_round, &params.proof)?;
 
         resp.data = vec![1u8];
         Ok(resp)
@@ -75,7 +308,7 @@ impl Tortilla {
         let ctx = self.context()?;
         let mut response = CallResponse::forward(&ctx.incoming_alkanes);
 
-        let consts = self.get_consts_value()?;
+        let params = decode_from_ctx!(ctx, SchemaAirdropRoundParameters)?;
 
         self.validate_protostone_tx(&ctx)?;
 
@@ -91,45 +324,205 @@ impl Tortilla {
         let merkle_proof = decode_from_vec!(witness_payload, SchemaMerkleProof)
             .context("MERKLE DISTRIBUTOR: Failed to decode merkle proof from witness data")?;
 
-        self.validate_proof(&merkle_proof)?;
+        let leaf = decode_from_vec!(merkle_proof.leaf, SchemaMerkleLeaf)?;
 
-        let mut ptr_used_leaf = self.get_used_leaf_pointer(&merkle_proof.leaf);
-        let used_leaf_check = ptr_used_leaf.get_value::<u8>();
+        self.assert_vout0_address(&tx, &leaf.address)?;
+
+        self.claim_airdrop_leaf(
+            params.round,
+            &merkle_proof,
+            leaf.address.clone(),
+            &mut response,
+        )?;
+
+        Ok(response)
+    }
+
+    pub fn claim_airdrop_signed(&self) -> Result<CallResponse> {
+        let ctx = self.context()?;
+        let mut response = CallResponse::forward(&ctx.incoming_alkanes);
+
+        let params = decode_from_ctx!(ctx, SchemaAirdropRoundParameters)?;
+
+        let tx = self
+            .get_serialized_transaction()
+            .map_err(|_| anyhow!("MERKLE DISTRIBUTOR: Failed to decode tx"))?;
+
+        let witness_payload = match extract_witness_payload(&tx) {
+            Some(bytes) => bytes,
+            None => return Err(anyhow!("MERKLE DISTRIBUTOR: Failed to decode tx")),
+        };
+
+        let signed_claim = decode_from_vec!(witness_payload, SchemaSignedMerkleClaim)
+            .context("MERKLE DISTRIBUTOR: Failed to decode signed claim from witness data")?;
+
+        //Claimed alkanes go wherever the protostone points, so it has to be the signed output
+        self.validate_protostone_tx_pointer(&ctx, signed_claim.recipient_vout)?;
+
+        let recipient_script = tx
+            .tx_out(signed_claim.recipient_vout as usize)
+            .context("TORTILLA: recipient output not present")?
+            .script_pubkey
+            .clone();
+
+        let recipient_address = Address::from_script(&recipient_script, DEPLOYMENT_NETWORK)
+            .context("TORTILLA: recipient output is not a valid address")?;
+
+        let leaf = decode_from_vec!(signed_claim.proof.leaf, SchemaMerkleLeaf)?;
+        let leaf_address = Address::from_str(&leaf.address)
+            .context("TORTILLA: invalid address in merkle leaf")?
+            .require_network(DEPLOYMENT_NETWORK)
+            .context("TORTILLA: merkle leaf address is for the wrong network")?;
+
+        let digest = calc_claim_intent_digest(
+            &ctx.myself,
+            params.round,
+            &signed_claim.proof.leaf,
+            recipient_script.as_bytes(),
+        );
+
+        verify_claim_signature(
+            &leaf_address,
+            digest,
+            &signed_claim.pubkey,
+            &signed_claim.signature,
+        )?;
+
+        //Vesting stays bound to the recipient so ClaimVested pays the same output later
+        self.claim_airdrop_leaf(
+            params.round,
+            &signed_claim.proof,
+            recipient_address.to_string(),
+            &mut response,
+        )?;
+
+        Ok(response)
+    }
+
+    pub fn claim_vested(&self) -> Result<CallResponse> {
+        let ctx = self.context()?;
+        let mut response = CallResponse::forward(&ctx.incoming_alkanes);
+
+        let params = decode_from_ctx!(ctx, SchemaAirdropLeafParameters)?;
+
+        self.validate_protostone_tx(&ctx)?;
+
+        let tx = self
+            .get_serialized_transaction()
+            .map_err(|_| anyhow!("MERKLE DISTRIBUTOR: Failed to decode tx"))?;
+
+        let mut position = self.get_vesting_position(params.round, &params.leaf)?;
+
+        self.assert_vout0_address(&tx, &position.address)?;
+
+        let released =
+            self.release_vested(params.round, &params.leaf, &mut position, &mut response)?;
 
         ensure!(
-            used_leaf_check == 0u8,
-            "MERKLE DISTRIBUTOR: This leaf has already been used to claim"
+            released > 0,
+            "TORTILLA: nothing has vested since the last claim"
         );
 
-        let leaf = decode_from_vec!(merkle_proof.leaf, SchemaMerkleLeaf)?;
+        Ok(response)
+    }
 
-        let caller_script_pub_key = tx
-            .tx_out(0)
-            .context("MERKLE DISTRIBUTOR: vout #0 not present")?
-            .clone()
-            .script_pubkey;
+    pub fn get_airdrop_vesting(&self) -> Result<CallResponse> {
+        let ctx = self.context()?;
+        let mut response = CallResponse::forward(&ctx.incoming_alkanes);
 
-        let tx_address = Address::from_script(&caller_script_pub_key, DEPLOYMENT_NETWORK)?;
+        let params = decode_from_ctx!(ctx, SchemaAirdropLeafParameters)?;
+        let consts = self.get_consts_value()?;
+
+        let position = self.get_vesting_position(params.round, &params.leaf)?;
+        let vested = calc_vested_amount(
+            position.total,
+            position.start_height,
+            &consts.airdrop_vesting,
+            self.height(),
+        );
+
+        response.data = borsh::to_vec(&SchemaAirdropVestingView {
+            total: position.total,
+            vested,
+            claimed: position.claimed,
+            remaining: position.total.saturating_sub(position.claimed),
+        })?;
+
+        Ok(response)
+    }
+
+    //Permissionless once the round's claim window is over. Hold treasuries get the remainder
+    //minted to the contract, redistribute treasuries get it queued for the emission accumulator
+    pub fn sweep_airdrop(&self) -> Result<CallResponse> {
+        let ctx = self.context()?;
+        let response = CallResponse::forward(&ctx.incoming_alkanes);
+
+        let params = decode_from_ctx!(ctx, SchemaAirdropRoundParameters)?;
+        let consts = self.get_consts_value()?;
+        let mut airdrop_round = self.get_airdrop_round(params.round)?;
 
         ensure!(
-            tx_address.to_string() == leaf.address,
-            "MERKLE DISTRIBUTOR: vout #0 doesnt contain the address in merkle proof"
+            self.height() > airdrop_round.end_height,
+            "TORTILLA: airdrop claim window has not expired yet"
         );
 
-        ptr_used_leaf.set_value(1u8);
+        let unclaimed = airdrop_round
+            .total
+            .saturating_sub(airdrop_round.claimed)
+            .saturating_sub(airdrop_round.swept);
 
-        //mint
+        ensure!(unclaimed > 0, "TORTILLA: nothing left to sweep");
 
-        self.controlled_mint_contract_mint_new(
-            &response,
-            consts.tortilla_alkane_id.into(),
-            leaf.amount,
-        )?;
+        let minted = self.get_treasury_state()?.policy == TreasuryPolicy::Hold;
+
+        if minted {
+            self.controlled_mint_contract_mint_new(
+                &response,
+                consts.tortilla_alkane_id.into(),
+                unclaimed,
+            )?;
+        }
+
+        self.record_treasury_inflow(TreasuryInflowSource::AirdropSweep, unclaimed, minted)?;
+
+        airdrop_round.swept = airdrop_round.swept.saturating_add(unclaimed);
+        self.set_airdrop_round(params.round, &airdrop_round)?;
+
+        Ok(response)
+    }
+
+    pub fn get_airdrop_stats(&self) -> Result<CallResponse> {
+        let ctx = self.context()?;
+        let mut response = CallResponse::forward(&ctx.incoming_alkanes);
+
+        let params = decode_from_ctx!(ctx, SchemaAirdropRoundParameters)?;
+        let airdrop_round = self.get_airdrop_round(params.round)?;
+
+        let unclaimed = airdrop_round
+            .total
+            .saturating_sub(airdrop_round.claimed)
+            .saturating_sub(airdrop_round.swept);
+
+        let expired = if self.height() > airdrop_round.end_height {
+            unclaimed
+        } else {
+            0u128
+        };
+
+        response.data = borsh::to_vec(&SchemaAirdropStatsView {
+            round: airdrop_round,
+            unclaimed,
+            expired,
+        })?;
+
+        Ok(response)
+    }
+
+    pub fn get_airdrop_round_count(&self) -> Result<CallResponse> {
+        let ctx = self.context()?;
+        let mut response = CallResponse::forward(&ctx.incoming_alkanes);
 
-        response.alkanes.0.push(AlkaneTransfer {
-            id: consts.tortilla_alkane_id.into(),
-            value: leaf.amount,
-        });
+        response.data = borsh::to_vec(&self.get_airdrop_round_count_pointer().get_value::<u32>())?;
 
         Ok(response)
     }
@@ -2,12 +2,89 @@ use borsh::{BorshDeserialize, BorshSerialize};
 
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
 pub struct SchemaMerkleProof {
+    pub version: u8,
     pub leaf: Vec<u8>,
     pub proofs: Vec<Vec<u8>>,
 }
 
+//Lets the leaf owner route their claim to any output by signing over it
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct SchemaSignedMerkleClaim {
+    pub proof: SchemaMerkleProof,
+    pub recipient_vout: u32,
+    pub pubkey: Vec<u8>,
+    pub signature: Vec<u8>,
+}
+
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
 pub struct SchemaMerkleLeaf {
     pub address: String,
     pub amount: u128,
 }
+
+//Airdrops unlock nothing until the cliff, then linearly over vesting_blocks
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, Copy)]
+pub struct SchemaVestingSchedule {
+    pub cliff_blocks: u64,
+    pub vesting_blocks: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct SchemaVestingPosition {
+    pub address: String,
+    pub total: u128,
+    pub claimed: u128,
+    pub start_height: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct SchemaAirdropLeafParameters {
+    pub round: u32,
+    pub leaf: Vec<u8>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct SchemaAirdropVestingView {
+    pub total: u128,
+    pub vested: u128,
+    pub claimed: u128,
+    pub remaining: u128,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct SchemaAirdropRound {
+    pub merkle_root: Vec<u8>,
+    pub total: u128,
+    pub claimed: u128, //Full leaf amounts, including what is still vesting
+    pub swept: u128,
+    pub start_height: u64,
+    pub end_height: u64,
+    pub allow_v1_proofs: bool,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct SchemaAddAirdropRoundParameters {
+    pub merkle_root: Vec<u8>,
+    pub total: u128,
+    pub start_height: u64,
+    pub end_height: u64,
+    pub allow_v1_proofs: bool,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct SchemaAirdropRoundParameters {
+    pub round: u32,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct SchemaAirdropClaimCheckParameters {
+    pub round: u32,
+    pub proof: SchemaMerkleProof,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct SchemaAirdropStatsView {
+    pub round: SchemaAirdropRound,
+    pub unclaimed: u128,
+    pub expired: u128,
+}
@@ -1,9 +1,11 @@
+use crate::airdrop::schemas::SchemaVestingSchedule;
 use crate::Tortilla;
 use alkanes_runtime::runtime::AlkaneResponder;
 use alkanes_support::context::Context;
+use alkanes_support::id::AlkaneId;
 use alkanes_support::witness::find_witness_payload;
 use anyhow::{anyhow, Result};
-use bitcoin::Transaction;
+use bitcoin::{Address, Transaction};
 use ordinals::{Artifact, Runestone};
 use protorune_support::{protostone::Protostone, utils::consensus_decode};
 use sha2::{Digest, Sha256};
@@ -21,29 +23,76 @@ pub fn extract_witness_payload(tx: &Transaction) -> Option<Vec<u8>> {
     None
 }
 
-pub fn calc_merkle_root(leaf: &[u8], proofs: &[Vec<u8>]) -> [u8; 32] {
-    let mut node: Vec<u8> = leaf.to_vec();
+pub fn calc_vested_amount(
+    total: u128,
+    start_height: u64,
+    schedule: &SchemaVestingSchedule,
+    current_height: u64,
+) -> u128 {
+    let elapsed = current_height.saturating_sub(start_height);
 
-    for sib in proofs {
-        let (left, right) = if node <= *sib {
-            (&node, sib)
-        } else {
-            (sib, &node)
-        };
-        let mut hasher = Sha256::new();
-        hasher.update(left);
-        hasher.update(right);
-        node = hasher.finalize().to_vec();
+    if elapsed < schedule.cliff_blocks {
+        return 0;
+    }
+
+    if schedule.vesting_blocks == 0 {
+        return total;
     }
 
-    // convert Vec<u8> → [u8;32]
-    let mut root = [0u8; 32];
-    root.copy_from_slice(&node);
-    root
+    let unlocked_blocks = (elapsed - schedule.cliff_blocks).min(schedule.vesting_blocks) as u128;
+
+    total.saturating_mul(unlocked_blocks) / (schedule.vesting_blocks as u128)
+}
+
+//Roots are rebuilt in merkle_support, shared with the merkle distributor and the merkle-tree tool
+pub fn calc_merkle_root_for_version(
+    version: u8,
+    leaf: &[u8],
+    proofs: &[Vec<u8>],
+    allow_v1: bool,
+) -> Result<[u8; 32]> {
+    merkle_support::calc_merkle_root_for_version(version, leaf, proofs, allow_v1)
+        .map_err(|e| anyhow!("TORTILLA: {e}"))
+}
+
+//What the leaf owner signs to let someone else's output receive their claim
+pub fn calc_claim_intent_digest(
+    contract: &AlkaneId,
+    round: u32,
+    leaf: &[u8],
+    recipient_script: &[u8],
+) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(b"TACOCLICKER/airdrop-claim");
+    hasher.update(contract.block.to_le_bytes());
+    hasher.update(contract.tx.to_le_bytes());
+    hasher.update(round.to_le_bytes());
+    hasher.update(Sha256::digest(leaf));
+    hasher.update(recipient_script);
+    hasher.finalize().into()
+}
+
+//Verified in merkle_support, shared with the merkle distributor
+pub fn verify_claim_signature(
+    leaf_address: &Address,
+    digest: [u8; 32],
+    pubkey: &[u8],
+    signature: &[u8],
+) -> Result<()> {
+    merkle_support::verify_claim_signature(leaf_address, digest, pubkey, signature)
+        .map_err(|e| anyhow!("TORTILLA: {e}"))
 }
 
 impl Tortilla {
     pub fn validate_protostone_tx(&self, ctx: &Context) -> Result<()> {
+        self.validate_protostone_tx_pointer(ctx, 0)
+    }
+
+    pub fn validate_protostone_tx_pointer(
+        &self,
+        ctx: &Context,
+        expected_pointer: u32,
+    ) -> Result<()> {
         let tx = consensus_decode::<Transaction>(&mut Cursor::new(self.transaction()))
             .map_err(|_| anyhow!("failed to decode transaction bytes"))?;
 
@@ -78,8 +127,10 @@ impl Tortilla {
             ));
         }
 
-        if pointer != 0 {
-            return Err(anyhow!("pointer must be set to 0! found {pointer}"));
+        if pointer != expected_pointer {
+            return Err(anyhow!(
+                "pointer must be set to {expected_pointer}! found {pointer}"
+            ));
         }
 
         Ok(())
@@ -29,6 +29,31 @@ pub const TORTILLA_PER_BLOCK: u128 = 1_500_000_000_000;
 //every 144 blocks, someone will win 216,000 tortilla... 10% of the ENTIRE DAILY tortilla production of the game.
 pub const SALSA_BLOCK_REWARD: u128 = 21_600_000_000_000;
 
+//TORTILLA mint rate limits, sized from the emission schedule above. The window is the longest one
+//controlled-mint accepts (4 weeks), a salsa prize goes out every 144 blocks
+pub const TORTILLA_MINT_WINDOW_BLOCKS: u64 = 4_032;
+pub const TORTILLA_WINDOW_EMISSION: u128 =
+    (TORTILLA_PER_BLOCK + SALSA_BLOCK_REWARD / 144) * TORTILLA_MINT_WINDOW_BLOCKS as u128;
+
+//One block can settle a taqueria that sat on a whole window of rewards, next to sweeping or claiming
+//a full airdrop round
+pub const TORTILLA_MAX_MINT_PER_BLOCK: u128 = TORTILLA_WINDOW_EMISSION + TORTILLA_AIRDROP_PREMINE;
+
+//Twice a window's emissions leaves room for rewards earned before the window but claimed inside it
+pub const TORTILLA_MAX_MINT_PER_WINDOW: u128 =
+    2 * TORTILLA_WINDOW_EMISSION + TORTILLA_AIRDROP_PREMINE;
+
+//TORTILLA lockups. Shortest lock is ~1 week, longest is ~1 year
+pub const MIN_LOCK_BLOCKS: u64 = 1_008;
+pub const MAX_LOCK_BLOCKS: u64 = 52_560;
+
+//1,000 TORTILLA locked for the max duration is worth 1 weight, decaying linearly to 0 at unlock
+pub const LOCK_TORTILLA_PER_WEIGHT: u128 = 100_000_000_000;
+
+//Treasury redistribution can run once a day and push at most a day's worth of emissions at a time
+pub const TREASURY_DISTRIBUTION_INTERVAL: u64 = 144;
+pub const TREASURY_MAX_DISTRIBUTION: u128 = TORTILLA_PER_BLOCK * 144;
+
 //Amount people must pay to funding address to register
 pub const FUNDING_PRICE_SATS: u64 = 21_000;
 
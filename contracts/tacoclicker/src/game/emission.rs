@@ -0,0 +1,95 @@
+//Accumulator math on the decoded emission states. The storage wrappers in game/storage.rs load the
+//states, run these and persist the result, so the math can be exercised without a runtime.
+
+use crate::consts::TORTILLA_PER_BLOCK;
+use crate::game::schemas::{SchemaGlobalEmissionState, SchemaTaqueriaEmissionState};
+use anyhow::{Context, Result};
+
+//Pushes every block since last_updated_block into the accumulator, split across the total weight
+pub fn accrue_emission(global: &mut SchemaGlobalEmissionState, now_block: u128) -> Result<()> {
+    if now_block == global.last_updated_block {
+        return Ok(());
+    }
+
+    let blocks = now_block
+        .checked_sub(global.last_updated_block)
+        .context("TORTILLA: block underflow")?;
+
+    //Mint only if someone is staked
+    if let Some(delta) = blocks
+        .saturating_mul(TORTILLA_PER_BLOCK)
+        .checked_div(global.total_weight)
+    {
+        global.acc_reward_per_weight = global.acc_reward_per_weight.saturating_add(delta);
+    }
+
+    global.last_updated_block = now_block;
+
+    Ok(())
+}
+
+// earned_since_last = weight * acc − reward_debt
+pub fn calc_earned(taq: &SchemaTaqueriaEmissionState, acc_reward_per_weight: u128) -> Result<u128> {
+    taq.taqueria_weight
+        .checked_mul(acc_reward_per_weight)
+        .context("TORTILLA: mul overflow at earned calculation")?
+        .checked_sub(taq.reward_debt)
+        .context("TORTILLA: sub underflow at earned calculation")
+}
+
+//Moves earned rewards into pending before the weight changes so they're kept at the old weight
+pub fn apply_weight_change(
+    global: &mut SchemaGlobalEmissionState,
+    taq: &mut SchemaTaqueriaEmissionState,
+    add_w: u128,
+    remove_w: u128,
+) -> Result<()> {
+    let earned = calc_earned(taq, global.acc_reward_per_weight)?;
+    taq.pending = taq.pending.saturating_add(earned);
+
+    taq.taqueria_weight = taq
+        .taqueria_weight
+        .saturating_add(add_w)
+        .checked_sub(remove_w)
+        .context("TORTILLA: taqueria weight underflow")?;
+    global.total_weight = global
+        .total_weight
+        .saturating_add(add_w)
+        .checked_sub(remove_w)
+        .context("TORTILLA: total weight underflow")?;
+    taq.reward_debt = taq
+        .taqueria_weight
+        .checked_mul(global.acc_reward_per_weight)
+        .context("TORTILLA: mul overflow at debt calc")?;
+
+    Ok(())
+}
+
+//Hands amount back to every weight pro rata. Like accrue_emission, what doesn't divide evenly is dropped
+pub fn redistribute_emission(global: &mut SchemaGlobalEmissionState, amount: u128) {
+    if global.total_weight == 0 {
+        return;
+    }
+
+    global.acc_reward_per_weight = global
+        .acc_reward_per_weight
+        .saturating_add(amount / global.total_weight);
+}
+
+//Zeroes pending and returns everything the taqueria had earned
+pub fn settle_claim(
+    global: &SchemaGlobalEmissionState,
+    taq: &mut SchemaTaqueriaEmissionState,
+) -> Result<u128> {
+    let amount = taq
+        .pending
+        .saturating_add(calc_earned(taq, global.acc_reward_per_weight)?);
+
+    taq.pending = 0;
+    taq.reward_debt = taq
+        .taqueria_weight
+        .checked_mul(global.acc_reward_per_weight)
+        .context("TORTILLA: overflow updating debt")?;
+
+    Ok(amount)
+}
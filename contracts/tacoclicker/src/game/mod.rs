@@ -1,4 +1,5 @@
 pub mod consts;
+pub mod emission;
 pub mod multipliers;
 pub mod schemas;
 pub mod storage;
@@ -141,3 +141,15 @@ pub struct SchemaCompleteGlobalState {
     pub emission_state: SchemaGlobalEmissionState,
     pub salsa_state: SchemaGlobalSalsaState,
 }
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct SchemaTaqueriaClaimEntry {
+    pub taqueria: SchemaAlkaneId,
+    pub amount: u128,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct SchemaClaimTortillaResponse {
+    pub total_claimed: u128,
+    pub claims: Vec<SchemaTaqueriaClaimEntry>,
+}
@@ -2,6 +2,7 @@
 
 use crate::consts::{SALSA_BLOCK_REWARD, TORTILLA_PER_BLOCK};
 use crate::game::consts::UPGRADES;
+use crate::game::emission::{accrue_emission, apply_weight_change, calc_earned, settle_claim};
 use crate::game::schemas::{
     SchemaGlobalEmissionState, SchemaGlobalSalsaState, SchemaTaqueriaEmissionState,
     SchemaUserUpgradesEntry,
@@ -71,28 +72,8 @@ impl Tortilla {
             decode_from_vec!(bytes, SchemaGlobalEmissionState)
                 .context("TORTILLA: failed to decode global emission state")?;
 
-        // 2. No‑op guard – already updated for this block ──────────────────────────
-        if now_block == state.last_updated_block {
-            return Ok(());
-        }
-
-        // 3. Gap length in blocks (checked math prevents underflow) ───────────────
-        let blocks = now_block
-            .checked_sub(state.last_updated_block)
-            .context("TORTILLA: block underflow")?;
-
-        // 4. Mint only if someone is staked ───────────────────────────────────────
-        if state.total_weight > 0 {
-            // ΔACC = blocks * EMISSION_PER_BLOCK * SCALE / total_weight
-            let delta = (blocks as u128).saturating_mul(TORTILLA_PER_BLOCK) / state.total_weight;
-
-            state.acc_reward_per_weight = state.acc_reward_per_weight.saturating_add(delta);
-        }
-
-        // 5. Slide the cursor forward ─────────────────────────────────────────────
-        state.last_updated_block = now_block;
+        accrue_emission(&mut state, now_block)?;
 
-        // 6. Persist the mutated struct back to storage ───────────────────────────
         let encoded =
             borsh::to_vec(&state).context("TORTILLA: failed to encode global emission state")?;
         ptr.set(Arc::new(encoded));
@@ -201,15 +182,59 @@ impl Tortilla {
         let taq: SchemaTaqueriaEmissionState =
             decode_from_vec!(taqueria_emission_state_bytes, SchemaTaqueriaEmissionState)?;
 
-        // earned_since_last = weight * acc_now − reward_debt
-        let earned_since_last = taq
-            .taqueria_weight
-            .checked_mul(acc_now)
-            .context("TORTILLA: mul ovf in calc_unclaimed")?
-            .checked_sub(taq.reward_debt)
-            .context("TORTILLA: underflow in calc_unclaimed")?;
+        let earned_since_last = calc_earned(&taq, acc_now)?;
+
+        //Locks nobody refreshed still carry their starting boost in the weight
+        let overcredit = self.calc_lock_boost_overcredit(taqueria, acc_now)?;
+
+        Ok(taq
+            .pending
+            .saturating_add(earned_since_last)
+            .saturating_sub(overcredit))
+    }
+
+    //Moves pending rewards forward before changing a taqueria's weight. Expects update_global to have run.
+    pub fn adjust_taqueria_weight(
+        &self,
+        taqueria: &SchemaAlkaneId,
+        add_w: u128,
+        remove_w: u128,
+    ) -> Result<()> {
+        let mut ptr_global_emission_state = self.get_global_emission_state_pointer();
+        let mut ptr_taqueria_emission_state = self.get_taqueria_emission_state_pointer(taqueria)?;
+
+        let global_emission_state_bytes = (*ptr_global_emission_state.get()).clone();
+        let taqueria_emission_state_bytes = (*ptr_taqueria_emission_state.get()).clone();
+
+        let mut global: SchemaGlobalEmissionState =
+            decode_from_vec!(global_emission_state_bytes, SchemaGlobalEmissionState)?;
+        let mut taq_state: SchemaTaqueriaEmissionState =
+            decode_from_vec!(taqueria_emission_state_bytes, SchemaTaqueriaEmissionState)?;
+
+        apply_weight_change(&mut global, &mut taq_state, add_w, remove_w)?;
+
+        ptr_taqueria_emission_state.set(Arc::new(borsh::to_vec(&taq_state)?));
+        ptr_global_emission_state.set(Arc::new(borsh::to_vec(&global)?));
+
+        Ok(())
+    }
+
+    //Zeroes out a taqueria's pending tortilla and returns how much it had. Expects update_global to have run.
+    pub fn settle_taqueria_claim(&self, taqueria: &SchemaAlkaneId) -> Result<u128> {
+        let global_bytes = (*self.get_global_emission_state_pointer().get()).clone();
+        let global: SchemaGlobalEmissionState =
+            decode_from_vec!(global_bytes, SchemaGlobalEmissionState)?;
+
+        let mut ptr_taqueria_emission_state = self.get_taqueria_emission_state_pointer(taqueria)?;
+        let taqueria_emission_state_bytes = (*ptr_taqueria_emission_state.get()).clone();
+        let mut taq: SchemaTaqueriaEmissionState =
+            decode_from_vec!(taqueria_emission_state_bytes, SchemaTaqueriaEmissionState)?;
+
+        let claim_amount = settle_claim(&global, &mut taq)?;
+
+        ptr_taqueria_emission_state.set(Arc::new(borsh::to_vec(&taq)?));
 
-        Ok(taq.pending.saturating_add(earned_since_last))
+        Ok(claim_amount)
     }
 
     pub fn update_global_salsa(&self, taqueria: &SchemaAlkaneId) -> Result<()> {
@@ -6,9 +6,14 @@ pub mod airdrop;
 pub mod consts;
 pub mod game;
 pub mod schemas;
+pub mod staking;
 pub mod token;
+pub mod treasury;
 pub mod utils;
 
+#[cfg(test)]
+mod tests;
+
 use alkanes_runtime::{declare_alkane, message::MessageDispatch, runtime::AlkaneResponder};
 use alkanes_support::id::AlkaneId;
 
@@ -25,22 +30,27 @@ use metashrew_support::utils::consensus_decode;
 use std::sync::Arc;
 use token::MintableToken;
 
-use crate::consts::{TORTILLA_CLAIM_WINDOW, TORTILLA_PER_BLOCK};
+use crate::consts::{
+    get_merkle_root_from_id, TORTILLA_MAX_MINT_PER_BLOCK, TORTILLA_MAX_MINT_PER_WINDOW,
+    TORTILLA_MINT_WINDOW_BLOCKS, TORTILLA_PER_BLOCK,
+};
 use crate::game::consts::UPGRADES;
 use crate::game::multipliers::{apply_multiplier, multiplier_from_seed};
 use crate::game::schemas::{
     SchemaBetOnBlockParameters, SchemaBetOnBlockResponse, SchemaBuyUpgradeParameters,
-    SchemaCompleteGlobalState, SchemaGetMultiplierFromHashParameters,
+    SchemaClaimTortillaResponse, SchemaCompleteGlobalState, SchemaGetMultiplierFromHashParameters,
     SchemaGetMultiplierFromHashResponse, SchemaGetTortillaPerBlockResponse,
     SchemaGetUnclaimedTortillaResponse, SchemaGlobalEmissionState, SchemaGlobalSalsaState,
-    SchemaTaqueriaEmissionState, SchemaTaqueriaSpecificParameters, SchemaUpgradesEntry,
-    SchemaUpgradesView, SchemaUserUpgradesView, UpgradeKind,
+    SchemaTaqueriaClaimEntry, SchemaTaqueriaEmissionState, SchemaTaqueriaSpecificParameters,
+    SchemaUpgradesEntry, SchemaUpgradesView, SchemaUserUpgradesView, UpgradeKind,
 };
 use crate::game::utils::{get_upgrade_by_id, get_upgrade_entry_by_id, get_upgrade_entry_by_id_mut};
 use crate::schemas::{
     SchemaAlkaneId, SchemaAlkaneList, SchemaControlledMintInitializationParameters,
-    SchemaTacoClickerConsts, SchemaTacoClickerInitializationParameters,
+    SchemaMintWindow, SchemaTacoClickerConsts, SchemaTacoClickerInitializationParameters,
+    SchemaTokenMetadata,
 };
+use crate::treasury::schemas::TreasuryInflowSource;
 use crate::utils::encoders::decode_from_ctx;
 use crate::utils::encoders::{address_from_txout, decode_from_vec, get_byte_array_from_inputs};
 use bitcoin::hashes::Hash;
@@ -75,7 +85,7 @@ impl Tortilla {
         found_alkanes
     }
 
-    fn get_taqueria_from_call(&self, response: &mut CallResponse) -> Result<SchemaAlkaneId> {
+    fn get_taquerias_from_call(&self, response: &mut CallResponse) -> Result<Vec<SchemaAlkaneId>> {
         let alkanes: Vec<SchemaAlkaneId> = response
             .alkanes
             .0
@@ -94,14 +104,46 @@ impl Tortilla {
 
         let alkane_list = SchemaAlkaneList { alkanes };
 
-        let taquerias = self.get_valid_taquerias_from_alkane_list(&alkane_list);
+        //The same taqueria can show up in more than one transfer, only act on it once
+        let mut taquerias: Vec<SchemaAlkaneId> = Vec::new();
+        for taqueria in self.get_valid_taquerias_from_alkane_list(&alkane_list) {
+            if !taquerias.contains(&taqueria) {
+                taquerias.push(taqueria);
+            }
+        }
+
+        ensure!(
+            !taquerias.is_empty(),
+            "TORTILLA: Could not derive a taqueria from provided alkanes in transaction"
+        );
+
+        Ok(taquerias)
+    }
+
+    fn get_taqueria_from_call(&self, response: &mut CallResponse) -> Result<SchemaAlkaneId> {
+        let taquerias = self.get_taquerias_from_call(response)?;
 
         let taqueria_alkane = taquerias.get(0).context(
             "TORTILLA: Could not derive a taqueria from provided alkanes in transaction",
         )?;
         Ok(*taqueria_alkane)
+    }
+
+    //The admin alkane just needs to be present in the call, it is forwarded back untouched
+    fn assert_admin(&self, response: &CallResponse) -> Result<()> {
+        let consts = self.get_consts_value()?;
+        let admin_alkane_id: AlkaneId = consts.admin_alkane_id.into();
 
-        // return something, or just Ok(alkane_list) if SchemaAlkaneId was a typo
+        ensure!(
+            response
+                .alkanes
+                .0
+                .iter()
+                .any(|transfer| transfer.id == admin_alkane_id && transfer.value > 0),
+            "TORTILLA: admin alkane not present in call"
+        );
+
+        Ok(())
     }
 
     //removes all transfers of "alkane" from response and returns a cumulative value of the amount collected (now controlled by the contract)
@@ -218,6 +260,45 @@ enum TortillaMessage {
     #[opcode(121)]
     ClaimAirdrop,
 
+    #[opcode(122)]
+    LockTortilla,
+
+    #[opcode(123)]
+    UnlockTortilla,
+
+    #[opcode(124)]
+    PokeTaqueriaLock,
+
+    #[opcode(125)]
+    GetTaqueriaLock,
+
+    #[opcode(126)]
+    GetTreasury,
+
+    #[opcode(127)]
+    DistributeTreasury,
+
+    #[opcode(128)]
+    ClaimVested,
+
+    #[opcode(129)]
+    GetAirdropVesting,
+
+    #[opcode(130)]
+    SweepAirdrop,
+
+    #[opcode(131)]
+    GetAirdropStats,
+
+    #[opcode(132)]
+    AddAirdropRound,
+
+    #[opcode(133)]
+    GetAirdropRoundCount,
+
+    #[opcode(134)]
+    ClaimAirdropSigned,
+
     #[opcode(1000)]
     #[returns(Vec<u8>)]
     GetData,
@@ -245,7 +326,7 @@ impl Tortilla {
             .map_err(|_| anyhow!("Contract already initialized"))?;
 
         let context = self.context()?;
-        let response = CallResponse::forward(&context.incoming_alkanes);
+        let mut response = CallResponse::forward(&context.incoming_alkanes);
 
         let init_params = decode_from_ctx!(context, SchemaTacoClickerInitializationParameters)?;
 
@@ -258,19 +339,67 @@ impl Tortilla {
                 token_symbol: "TORTILLA".to_string(),
                 premine: 0u128,
                 cap: u128::MAX,
+                max_mint_per_block: Some(TORTILLA_MAX_MINT_PER_BLOCK),
+                max_mint_per_window: Some(SchemaMintWindow {
+                    blocks: TORTILLA_MINT_WINDOW_BLOCKS,
+                    amount: TORTILLA_MAX_MINT_PER_WINDOW,
+                }),
+                metadata: Some(SchemaTokenMetadata {
+                    decimals: 8,
+                    description: Some(
+                        "Currency of Taco Clicker, earned by taquerias and spent on upgrades"
+                            .to_string(),
+                    ),
+                    image: None,
+                    external_url: None,
+                }),
+                mint_schedule: None,
+                premine_splits: None,
             }),
         )?;
 
+        //Whoever holds this alkane can run admin opcodes (ie: adding airdrop rounds)
+        let admin_alkane_id = self.clone_at_target(
+            response.alkanes.clone(),
+            init_params.controlled_mint_factory.into(),
+            0u128,
+            Some(&SchemaControlledMintInitializationParameters {
+                token_name: "TACOCLICKER ADMIN".to_string(),
+                token_symbol: "TACOADMIN".to_string(),
+                premine: 1u128,
+                cap: 1u128,
+                max_mint_per_block: None,
+                max_mint_per_window: None,
+                metadata: Some(SchemaTokenMetadata {
+                    decimals: 0,
+                    description: Some("Admin key for the Taco Clicker contract".to_string()),
+                    image: None,
+                    external_url: None,
+                }),
+                mint_schedule: None,
+                premine_splits: None,
+            }),
+        )?;
+
+        response.alkanes.0.push(AlkaneTransfer {
+            id: admin_alkane_id.into(),
+            value: 1u128,
+        });
+
         let consts = SchemaTacoClickerConsts {
             controlled_mint_factory: init_params.controlled_mint_factory,
             tortilla_alkane_id,
+            admin_alkane_id,
             merkle_root_id: init_params.merkle_root_id,
-            airdrop_end_height: self.height() + TORTILLA_CLAIM_WINDOW,
+            airdrop_vesting: init_params.airdrop_vesting,
         };
 
         let consumed_bytes = borsh::to_vec(&consts)?;
         self.get_consts_pointer().set(Arc::new(consumed_bytes));
 
+        self.create_treasury(init_params.treasury_policy)?;
+        self.create_initial_airdrop_round(get_merkle_root_from_id(init_params.merkle_root_id)?)?;
+
         let initial_global_emissions_state = SchemaGlobalEmissionState {
             total_weight: 0u128,
             acc_reward_per_weight: 0u128,
@@ -391,6 +520,18 @@ impl Tortilla {
                 token_symbol: "TAQUERIA".to_string(),
                 premine: 1u128,
                 cap: 1u128,
+                max_mint_per_block: None,
+                max_mint_per_window: None,
+                metadata: Some(SchemaTokenMetadata {
+                    decimals: 0,
+                    description: Some(
+                        "A Taco Clicker taqueria, holding it lets you play".to_string(),
+                    ),
+                    image: None,
+                    external_url: None,
+                }),
+                mint_schedule: None,
+                premine_splits: None,
             }),
         )?;
 
@@ -436,34 +577,10 @@ impl Tortilla {
         );
 
         self.update_global()?;
-
-        let mut ptr_global_emission_state = self.get_global_emission_state_pointer();
-        let mut ptr_taqueria_emission_state =
-            self.get_taqueria_emission_state_pointer(&taqueria)?;
-
-        let global_emission_state_bytes = (*ptr_global_emission_state.get()).clone();
-        let taqueria_emission_state_bytes = (*ptr_taqueria_emission_state.get()).clone();
-
-        let mut global: SchemaGlobalEmissionState =
-            decode_from_vec!(global_emission_state_bytes, SchemaGlobalEmissionState)?;
-        let mut taq_state: SchemaTaqueriaEmissionState =
-            decode_from_vec!(taqueria_emission_state_bytes, SchemaTaqueriaEmissionState)?;
-
-        let earned = taq_state
-            .taqueria_weight
-            .checked_mul(global.acc_reward_per_weight)
-            .context("TORTILLA: mul overflow at earned calculation")?
-            .checked_sub(taq_state.reward_debt)
-            .context("TORTILLA: sub underflow at earned calculation")?;
-        taq_state.pending = taq_state.pending.saturating_add(earned);
+        self.refresh_lock_boost(&taqueria)?;
 
         let add_w = get_upgrade_by_id(params.upgrade.into())?.weight;
-        taq_state.taqueria_weight = taq_state.taqueria_weight.saturating_add(add_w);
-        global.total_weight = global.total_weight.saturating_add(add_w);
-        taq_state.reward_debt = taq_state
-            .taqueria_weight
-            .checked_mul(global.acc_reward_per_weight)
-            .context("TORTILLA: mul overflow at debt calc")?;
+        self.adjust_taqueria_weight(&taqueria, add_w, 0u128)?;
 
         //Refund user change that wasnt used to buy the upgrade
         response.alkanes.0.push(AlkaneTransfer {
@@ -473,6 +590,9 @@ impl Tortilla {
                 .context("TORTILLA: checked sub failed for refund")?,
         });
 
+        //Tortilla spent on the upgrade now belongs to the treasury
+        self.record_treasury_inflow(TreasuryInflowSource::Upgrades, entry.next_price, true)?;
+
         entry.next_price = entry
             .next_price
             .checked_mul(3)
@@ -482,8 +602,6 @@ impl Tortilla {
         entry.amount = entry.amount.saturating_add(1u128);
 
         ptr_taqueria_upgrades.set(Arc::new(borsh::to_vec(&upgrades)?));
-        ptr_taqueria_emission_state.set(Arc::new(borsh::to_vec(&taq_state)?));
-        ptr_global_emission_state.set(Arc::new(borsh::to_vec(&global)?));
 
         Ok(response)
     }
@@ -497,6 +615,10 @@ impl Tortilla {
         self.proof_of_click(&taqueria, params.nonce_found_poc)
             .context("TORTILLA: Proof‑of‑Click failed")?;
 
+        //Bets stake the settled amount, so the accumulator and the lock boost have to be current
+        self.update_global()?;
+        self.refresh_lock_boost(&taqueria)?;
+
         let bhash_bytes = self.blockhash()?;
         let multiplier = multiplier_from_seed(&bhash_bytes)?;
 
@@ -530,6 +652,8 @@ impl Tortilla {
 
         self.update_global_salsa(&taqueria)?;
 
+        self.record_treasury_inflow(TreasuryInflowSource::LostBets, lost_amt, false)?;
+
         // persist taqueria state
         ptr_taq.set(Arc::new(borsh::to_vec(&taq)?));
 
@@ -546,48 +670,48 @@ impl Tortilla {
     pub fn claim_tortilla(&self) -> Result<CallResponse> {
         let ctx = self.context()?;
         let mut response = CallResponse::forward(&ctx.incoming_alkanes);
-        let taqueria = self.get_taqueria_from_call(&mut response)?;
 
-        let consts = self.get_consts_value()?;
+        //Every valid taqueria passed in gets settled. The taqueria alkanes themselves are
+        //returned to the caller untouched since the response forwards all incoming alkanes.
+        let taquerias = self.get_taquerias_from_call(&mut response)?;
 
-        let ptr_global_emission_state = self.get_global_emission_state_pointer();
-        let mut ptr_taqueria_emission_state =
-            self.get_taqueria_emission_state_pointer(&taqueria.into())?;
+        let consts = self.get_consts_value()?;
 
         self.update_global()?;
 
-        let claim_amount = self.calc_unclaimed_tortilla(&taqueria.into())?;
-        ensure!(claim_amount > 0, "TORTILLA: nothing to claim");
-
-        let global_emission_state_bytes = (*ptr_global_emission_state.get()).clone();
+        let mut claims: Vec<SchemaTaqueriaClaimEntry> = Vec::with_capacity(taquerias.len());
+        let mut total_claimed: u128 = 0;
 
-        let taqueria_emission_state_bytes = (*ptr_taqueria_emission_state.get()).clone();
+        for taqueria in taquerias {
+            self.refresh_lock_boost(&taqueria)?;
+            let amount = self.settle_taqueria_claim(&taqueria)?;
 
-        let global_emission_state: SchemaGlobalEmissionState =
-            decode_from_vec!(global_emission_state_bytes, SchemaGlobalEmissionState)?;
-        let mut taqueria_emission_state: SchemaTaqueriaEmissionState =
-            decode_from_vec!(taqueria_emission_state_bytes, SchemaTaqueriaEmissionState)?;
+            total_claimed = total_claimed
+                .checked_add(amount)
+                .context("TORTILLA: overflow while summing claimed tortilla")?;
 
-        taqueria_emission_state.pending = 0;
-        taqueria_emission_state.reward_debt = taqueria_emission_state
-            .taqueria_weight
-            .checked_mul(global_emission_state.acc_reward_per_weight)
-            .context("TORTILLA: overflow updating debt")?;
+            claims.push(SchemaTaqueriaClaimEntry { taqueria, amount });
+        }
 
-        ptr_taqueria_emission_state.set(Arc::new(borsh::to_vec(&taqueria_emission_state)?));
+        ensure!(total_claimed > 0, "TORTILLA: nothing to claim");
 
-        //Mint the tortilla
+        //Mint the tortilla for every taqueria in one go
         self.controlled_mint_contract_mint_new(
             &response,
             consts.tortilla_alkane_id.into(),
-            claim_amount,
+            total_claimed,
         )?;
 
         response.alkanes.0.push(AlkaneTransfer {
             id: consts.tortilla_alkane_id.into(),
-            value: claim_amount,
+            value: total_claimed,
         });
 
+        response.data = borsh::to_vec(&SchemaClaimTortillaResponse {
+            total_claimed,
+            claims,
+        })?;
+
         Ok(response)
     }
 
@@ -1,5 +1,8 @@
 use alkanes_support::id::AlkaneId;
 use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::airdrop::schemas::SchemaVestingSchedule;
+use crate::treasury::schemas::TreasuryPolicy;
 /*
     Schema alkaneid uses u32's which have a max value of 4b. This fits well into the constrains of BTC (for block and sequence value)
     Uses something like u128 in storage is very wasteful. Tx uses a u64 because the sequence pointer CAN eventually overflow 4b, but
@@ -16,14 +19,17 @@ pub struct SchemaAlkaneId {
 pub struct SchemaTacoClickerInitializationParameters {
     pub controlled_mint_factory: SchemaAlkaneId,
     pub merkle_root_id: u8,
+    pub treasury_policy: TreasuryPolicy,
+    pub airdrop_vesting: SchemaVestingSchedule,
 }
 
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
 pub struct SchemaTacoClickerConsts {
     pub controlled_mint_factory: SchemaAlkaneId,
     pub tortilla_alkane_id: SchemaAlkaneId,
+    pub admin_alkane_id: SchemaAlkaneId,
     pub merkle_root_id: u8,
-    pub airdrop_end_height: u64,
+    pub airdrop_vesting: SchemaVestingSchedule,
 }
 
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
@@ -46,11 +52,68 @@ pub struct SchemaControlledMintInitializationParameters {
     pub token_symbol: String,
     pub premine: u128,
     pub cap: u128,
+    pub max_mint_per_block: Option<u128>,
+    pub max_mint_per_window: Option<SchemaMintWindow>,
+    pub metadata: Option<SchemaTokenMetadata>,
+    pub mint_schedule: Option<SchemaMintSchedule>,
+    pub premine_splits: Option<Vec<SchemaMintSplit>>,
 }
+
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
-pub struct SchemaInitializeMerkleDistributorParameters {
-    pub merkle_root: Vec<u8>,
+pub enum SchemaMintSchedule {
+    Linear {
+        start_height: u64,
+        end_height: u64,
+        amount: u128,
+    },
+    Stepwise {
+        steps: Vec<SchemaUnlockStep>,
+    },
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct SchemaUnlockStep {
+    pub height: u64,
+    pub amount: u128,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct SchemaMintSplit {
+    pub amount: u128,
+    pub output: u32,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct SchemaTokenMetadata {
+    pub decimals: u8,
+    pub description: Option<String>,
+    pub image: Option<SchemaTokenImage>,
+    pub external_url: Option<String>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub enum SchemaTokenImage {
+    Uri(String),
+    Inline(Vec<u8>),
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct SchemaMintWindow {
+    pub blocks: u64,
+    pub amount: u128,
+}
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct SchemaDistributorDeposit {
     pub alkane_id: SchemaAlkaneId,
     pub amount: u128,
+}
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct SchemaInitializeMerkleDistributorParameters {
+    pub merkle_root: Vec<u8>,
+    pub deposits: Vec<SchemaDistributorDeposit>,
     pub block_end: u128,
+    pub allow_v1_proofs: bool,
+    //Becomes the owner instead of the caller. Required when initializing straight from a
+    //transaction, where the caller is 0:0
+    pub owner_address: Option<String>,
 }
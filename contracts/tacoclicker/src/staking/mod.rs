@@ -0,0 +1,3 @@
+pub mod responder;
+pub mod schemas;
+pub mod utils;
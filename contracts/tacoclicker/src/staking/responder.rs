@@ -0,0 +1,201 @@
+use crate::consts::{MAX_LOCK_BLOCKS, MIN_LOCK_BLOCKS};
+use crate::game::schemas::{
+    SchemaGlobalEmissionState, SchemaTaqueriaEmissionState, SchemaTaqueriaSpecificParameters,
+};
+use crate::schemas::SchemaAlkaneId;
+use crate::staking::{
+    schemas::{SchemaLockTortillaParameters, SchemaTaqueriaLockState, SchemaTaqueriaLockView},
+    utils::{calc_lock_boost, calc_lock_boost_overcredit, refresh_lock},
+};
+use crate::utils::encoders::{decode_from_ctx, decode_from_vec, get_byte_array_from_inputs};
+use crate::Tortilla;
+use alkanes_runtime::{runtime::AlkaneResponder, storage::StoragePointer};
+use alkanes_support::{parcel::AlkaneTransfer, response::CallResponse};
+use anyhow::{ensure, Context, Result};
+use borsh::BorshDeserialize;
+use metashrew_support::index_pointer::KeyValuePointer;
+use std::sync::Arc;
+
+//STORAGE GETTERS
+impl Tortilla {
+    fn get_taqueria_lock_pointer(&self, taqueria: &SchemaAlkaneId) -> Result<StoragePointer> {
+        Ok(StoragePointer::from_keyword("/taqueria_lock").select(
+            &borsh::to_vec(taqueria).context("TORTILLA: failed to get taqueria lock pointer")?,
+        ))
+    }
+
+    fn get_taqueria_lock_state(
+        &self,
+        taqueria: &SchemaAlkaneId,
+    ) -> Result<SchemaTaqueriaLockState> {
+        let bytes = (*self.get_taqueria_lock_pointer(taqueria)?.get()).clone();
+
+        //Taquerias that never locked anything have no entry yet
+        if bytes.is_empty() {
+            return Ok(SchemaTaqueriaLockState::default());
+        }
+
+        decode_from_vec!(bytes, SchemaTaqueriaLockState)
+    }
+
+    pub fn get_total_locked_tortilla_pointer(&self) -> StoragePointer {
+        StoragePointer::from_keyword("/total_locked_tortilla")
+    }
+
+    pub fn get_total_locked_tortilla(&self) -> u128 {
+        self.get_total_locked_tortilla_pointer().get_value::<u128>()
+    }
+
+    //Overcredit a view would take back if the lock were refreshed at acc_now
+    pub fn calc_lock_boost_overcredit(
+        &self,
+        taqueria: &SchemaAlkaneId,
+        acc_now: u128,
+    ) -> Result<u128> {
+        let lock = self.get_taqueria_lock_state(taqueria)?;
+        Ok(calc_lock_boost_overcredit(&lock, self.height(), acc_now))
+    }
+
+    //Expects update_global to have run. Every path that settles a taqueria's rewards must call this first.
+    pub fn refresh_lock_boost(&self, taqueria: &SchemaAlkaneId) -> Result<()> {
+        let mut ptr_global_emission_state = self.get_global_emission_state_pointer();
+        let mut ptr_taqueria_emission_state = self.get_taqueria_emission_state_pointer(taqueria)?;
+
+        let global_bytes = (*ptr_global_emission_state.get()).clone();
+        let taqueria_bytes = (*ptr_taqueria_emission_state.get()).clone();
+
+        let mut global: SchemaGlobalEmissionState =
+            decode_from_vec!(global_bytes, SchemaGlobalEmissionState)?;
+        let mut taq: SchemaTaqueriaEmissionState =
+            decode_from_vec!(taqueria_bytes, SchemaTaqueriaEmissionState)?;
+        let mut lock = self.get_taqueria_lock_state(taqueria)?;
+
+        refresh_lock(&mut global, &mut taq, &mut lock, self.height())?;
+
+        ptr_global_emission_state.set(Arc::new(borsh::to_vec(&global)?));
+        ptr_taqueria_emission_state.set(Arc::new(borsh::to_vec(&taq)?));
+        self.get_taqueria_lock_pointer(taqueria)?
+            .set(Arc::new(borsh::to_vec(&lock)?));
+
+        Ok(())
+    }
+}
+
+impl Tortilla {
+    pub fn lock_tortilla(&self) -> Result<CallResponse> {
+        let ctx = self.context()?;
+        let mut response = CallResponse::forward(&ctx.incoming_alkanes);
+        let taqueria = self.get_taqueria_from_call(&mut response)?;
+        let params = decode_from_ctx!(ctx, SchemaLockTortillaParameters)?;
+        let consts = self.get_consts_value()?;
+
+        ensure!(
+            params.duration >= MIN_LOCK_BLOCKS && params.duration <= MAX_LOCK_BLOCKS,
+            "TORTILLA: lock duration must be between {MIN_LOCK_BLOCKS} and {MAX_LOCK_BLOCKS} blocks"
+        );
+
+        let tortilla_locked =
+            self.collapse_transfers_for_alkane(consts.tortilla_alkane_id, &mut response)?;
+
+        ensure!(tortilla_locked > 0, "TORTILLA: no tortilla sent to lock");
+
+        self.update_global()?;
+
+        //Settle the existing lock before its amount and unlock height change
+        self.refresh_lock_boost(&taqueria)?;
+
+        let mut lock = self.get_taqueria_lock_state(&taqueria)?;
+
+        //Adding to a lock can only push the unlock height further out
+        lock.amount = lock
+            .amount
+            .checked_add(tortilla_locked)
+            .context("TORTILLA: overflow adding to lock")?;
+        lock.unlock_height = lock.unlock_height.max(self.height() + params.duration);
+
+        let total_locked = self
+            .get_total_locked_tortilla()
+            .checked_add(tortilla_locked)
+            .context("TORTILLA: overflow adding to total locked tortilla")?;
+        self.get_total_locked_tortilla_pointer()
+            .set_value::<u128>(total_locked);
+
+        self.get_taqueria_lock_pointer(&taqueria)?
+            .set(Arc::new(borsh::to_vec(&lock)?));
+
+        self.refresh_lock_boost(&taqueria)?;
+
+        Ok(response)
+    }
+
+    pub fn unlock_tortilla(&self) -> Result<CallResponse> {
+        let ctx = self.context()?;
+        let mut response = CallResponse::forward(&ctx.incoming_alkanes);
+        let taqueria = self.get_taqueria_from_call(&mut response)?;
+        let consts = self.get_consts_value()?;
+
+        let lock = self.get_taqueria_lock_state(&taqueria)?;
+
+        ensure!(lock.amount > 0, "TORTILLA: taqueria has no locked tortilla");
+        ensure!(
+            self.height() >= lock.unlock_height,
+            "TORTILLA: lock has not expired yet, unlocks at height {}",
+            lock.unlock_height
+        );
+
+        self.update_global()?;
+
+        //Boost is already 0 past the unlock height, this just takes it out of the weights
+        self.refresh_lock_boost(&taqueria)?;
+
+        self.get_taqueria_lock_pointer(&taqueria)?
+            .set(Arc::new(
+                borsh::to_vec(&SchemaTaqueriaLockState::default())?,
+            ));
+
+        self.get_total_locked_tortilla_pointer()
+            .set_value::<u128>(self.get_total_locked_tortilla().saturating_sub(lock.amount));
+
+        response.alkanes.0.push(AlkaneTransfer {
+            id: consts.tortilla_alkane_id.into(),
+            value: lock.amount,
+        });
+
+        Ok(response)
+    }
+
+    //Permissionless so stale boosts can be decayed by anyone, not just the owner
+    pub fn poke_taqueria_lock(&self) -> Result<CallResponse> {
+        let ctx = self.context()?;
+        let response = CallResponse::forward(&ctx.incoming_alkanes);
+        let params = decode_from_ctx!(ctx, SchemaTaqueriaSpecificParameters)?;
+
+        ensure!(
+            self.get_taquerias_pointer(&params.taqueria)?
+                .get_value::<u8>()
+                == 1u8,
+            "TORTILLA: taqueria not found"
+        );
+
+        self.update_global()?;
+        self.refresh_lock_boost(&params.taqueria)?;
+
+        Ok(response)
+    }
+
+    pub fn get_taqueria_lock(&self) -> Result<CallResponse> {
+        let ctx = self.context()?;
+        let mut response = CallResponse::forward(&ctx.incoming_alkanes);
+        let params = decode_from_ctx!(ctx, SchemaTaqueriaSpecificParameters)?;
+
+        let lock = self.get_taqueria_lock_state(&params.taqueria)?;
+        let current_boost_weight = calc_lock_boost(lock.amount, lock.unlock_height, self.height());
+
+        response.data = borsh::to_vec(&SchemaTaqueriaLockView {
+            lock,
+            current_boost_weight,
+        })?;
+
+        Ok(response)
+    }
+}
@@ -0,0 +1,21 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Default)]
+pub struct SchemaTaqueriaLockState {
+    pub amount: u128,
+    pub unlock_height: u64,
+    pub boost_weight: u128, //Boost currently folded into taqueria_weight, refreshed on every touch
+    pub boost_height: u64,  //Height boost_weight was last set at
+    pub boost_acc: u128,    //acc_reward_per_weight when boost_weight was last set
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct SchemaLockTortillaParameters {
+    pub duration: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct SchemaTaqueriaLockView {
+    pub lock: SchemaTaqueriaLockState,
+    pub current_boost_weight: u128,
+}
@@ -0,0 +1,103 @@
+use crate::consts::{LOCK_TORTILLA_PER_WEIGHT, MAX_LOCK_BLOCKS};
+use crate::game::emission::{apply_weight_change, redistribute_emission};
+use crate::game::schemas::{SchemaGlobalEmissionState, SchemaTaqueriaEmissionState};
+use crate::staking::schemas::SchemaTaqueriaLockState;
+use anyhow::Result;
+
+//Vote-escrow style boost: full weight with MAX_LOCK_BLOCKS remaining, decaying linearly to 0 at unlock_height
+pub fn calc_lock_boost(amount: u128, unlock_height: u64, current_height: u64) -> u128 {
+    if current_height >= unlock_height {
+        return 0;
+    }
+
+    let remaining = (unlock_height - current_height).min(MAX_LOCK_BLOCKS) as u128;
+
+    amount.saturating_mul(remaining) / (MAX_LOCK_BLOCKS as u128) / LOCK_TORTILLA_PER_WEIGHT
+}
+
+//Average of calc_lock_boost over the heights from_height..to_height, used to settle a stretch where the
+//boost decayed but the weight still carried the boost from from_height
+pub fn calc_lock_boost_average(
+    amount: u128,
+    unlock_height: u64,
+    from_height: u64,
+    to_height: u64,
+) -> u128 {
+    if to_height <= from_height {
+        return calc_lock_boost(amount, unlock_height, from_height);
+    }
+
+    let blocks = (to_height - from_height) as u128;
+    let end = to_height.min(unlock_height);
+    let mut remaining_sum: u128 = 0;
+
+    if end > from_height {
+        //Heights up to unlock_height - MAX_LOCK_BLOCKS sit at the full boost, the rest decay by 1 per block
+        let flat_bound = unlock_height
+            .checked_sub(MAX_LOCK_BLOCKS)
+            .map(|h| h + 1)
+            .unwrap_or(0);
+        let flat_end = end.min(flat_bound).max(from_height);
+
+        remaining_sum += (flat_end - from_height) as u128 * MAX_LOCK_BLOCKS as u128;
+
+        if end > flat_end {
+            let count = (end - flat_end) as u128;
+            let first = (unlock_height - flat_end) as u128;
+            let last = (unlock_height - (end - 1)) as u128;
+            remaining_sum += count * (first + last) / 2;
+        }
+    }
+
+    amount.saturating_mul(remaining_sum)
+        / blocks
+        / (MAX_LOCK_BLOCKS as u128)
+        / LOCK_TORTILLA_PER_WEIGHT
+}
+
+//Rewards accrued since the boost was last set were paid on the boost at that height, but the
+//boost decayed in between. This is how much was paid above the average boost over those blocks.
+pub fn calc_lock_boost_overcredit(
+    lock: &SchemaTaqueriaLockState,
+    height: u64,
+    acc_now: u128,
+) -> u128 {
+    if lock.boost_weight == 0 || height <= lock.boost_height {
+        return 0;
+    }
+
+    let average_boost =
+        calc_lock_boost_average(lock.amount, lock.unlock_height, lock.boost_height, height);
+
+    lock.boost_weight
+        .saturating_sub(average_boost)
+        .saturating_mul(acc_now.saturating_sub(lock.boost_acc))
+}
+
+//Settles pending rewards at the average decayed boost and folds the current boost into the weight.
+//The overcredit goes back into the accumulator for every weight, so decayed emissions aren't lost.
+//Expects the accumulator to be current for height.
+pub fn refresh_lock(
+    global: &mut SchemaGlobalEmissionState,
+    taq: &mut SchemaTaqueriaEmissionState,
+    lock: &mut SchemaTaqueriaLockState,
+    height: u64,
+) -> Result<()> {
+    let overcredit = calc_lock_boost_overcredit(lock, height, global.acc_reward_per_weight);
+    let boost_weight = calc_lock_boost(lock.amount, lock.unlock_height, height);
+
+    //Settles pending at the old weight, the overcredit then takes back what the decay owed
+    apply_weight_change(global, taq, boost_weight, lock.boost_weight)?;
+
+    let taken_back = overcredit.min(taq.pending);
+    taq.pending -= taken_back;
+
+    //Handed out at the new weights, this taqueria included since its reward debt stays behind
+    redistribute_emission(global, taken_back);
+
+    lock.boost_weight = boost_weight;
+    lock.boost_height = height;
+    lock.boost_acc = global.acc_reward_per_weight;
+
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use crate::consts::{
+    SALSA_BLOCK_REWARD, TORTILLA_AIRDROP_PREMINE, TORTILLA_MAX_MINT_PER_BLOCK,
+    TORTILLA_MAX_MINT_PER_WINDOW, TORTILLA_MINT_WINDOW_BLOCKS, TORTILLA_PER_BLOCK,
+};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn test_tortilla_mint_window_is_accepted_by_controlled_mint() {
+    //controlled-mint rejects windows longer than 4032 blocks at initialize
+    assert!(TORTILLA_MINT_WINDOW_BLOCKS > 0 && TORTILLA_MINT_WINDOW_BLOCKS <= 4_032);
+}
+
+#[wasm_bindgen_test]
+fn test_tortilla_mint_limits_cover_a_busy_block() {
+    //A day of rewards claimed at once, the salsa prize and a whole airdrop round in the same block
+    let busy_block = TORTILLA_PER_BLOCK * 144 + SALSA_BLOCK_REWARD + TORTILLA_AIRDROP_PREMINE;
+
+    assert!(TORTILLA_MAX_MINT_PER_BLOCK >= busy_block);
+    assert!(TORTILLA_MAX_MINT_PER_WINDOW >= TORTILLA_MAX_MINT_PER_BLOCK);
+
+    //Still a real bound, well under a year of the game's emissions
+    assert!(TORTILLA_MAX_MINT_PER_WINDOW < TORTILLA_PER_BLOCK * 52_560);
+}
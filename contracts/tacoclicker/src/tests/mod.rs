@@ -0,0 +1,2 @@
+mod mint_limits;
+mod staking;
@@ -0,0 +1,299 @@
+use crate::consts::{
+    LOCK_TORTILLA_PER_WEIGHT, MAX_LOCK_BLOCKS, MIN_LOCK_BLOCKS, TORTILLA_PER_BLOCK,
+};
+use crate::game::emission::{accrue_emission, settle_claim};
+use crate::game::schemas::{SchemaGlobalEmissionState, SchemaTaqueriaEmissionState};
+use crate::staking::schemas::SchemaTaqueriaLockState;
+use crate::staking::utils::{
+    calc_lock_boost, calc_lock_boost_average, calc_lock_boost_overcredit, refresh_lock,
+};
+use anyhow::Result;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+//Locked for the max duration this is worth 10 weight at the start
+const LOCK_AMOUNT: u128 = 10 * LOCK_TORTILLA_PER_WEIGHT;
+
+//Rounding in the accumulator drops less than the total weight per update, far below this
+const DUST: u128 = 1_000_000;
+
+struct Taqueria {
+    emission: SchemaTaqueriaEmissionState,
+    lock: SchemaTaqueriaLockState,
+    claimed: u128,
+}
+
+//Two taquerias of weight 1 driven the way the contract drives them: update_global, then
+//refresh_lock_boost, then whatever the call does
+struct Game {
+    global: SchemaGlobalEmissionState,
+    taquerias: [Taqueria; 2],
+    height: u64,
+}
+
+impl Game {
+    fn new() -> Self {
+        let taqueria = || Taqueria {
+            emission: SchemaTaqueriaEmissionState {
+                taqueria_weight: 1,
+                reward_debt: 0,
+                pending: 0,
+                last_poc_hash: vec![],
+            },
+            lock: SchemaTaqueriaLockState::default(),
+            claimed: 0,
+        };
+
+        Self {
+            global: SchemaGlobalEmissionState {
+                total_weight: 2,
+                acc_reward_per_weight: 0,
+                last_updated_block: 0,
+            },
+            taquerias: [taqueria(), taqueria()],
+            height: 0,
+        }
+    }
+
+    fn refresh(&mut self, index: usize, height: u64) -> Result<()> {
+        self.height = height;
+        accrue_emission(&mut self.global, height as u128)?;
+
+        let taqueria = &mut self.taquerias[index];
+        refresh_lock(
+            &mut self.global,
+            &mut taqueria.emission,
+            &mut taqueria.lock,
+            height,
+        )
+    }
+
+    fn lock(&mut self, index: usize, height: u64, amount: u128, duration: u64) -> Result<()> {
+        self.refresh(index, height)?;
+
+        let lock = &mut self.taquerias[index].lock;
+        lock.amount += amount;
+        lock.unlock_height = lock.unlock_height.max(height + duration);
+
+        self.refresh(index, height)
+    }
+
+    fn poke(&mut self, index: usize, height: u64) -> Result<()> {
+        self.refresh(index, height)
+    }
+
+    fn unlock(&mut self, index: usize, height: u64) -> Result<()> {
+        assert!(height >= self.taquerias[index].lock.unlock_height);
+
+        self.refresh(index, height)?;
+        self.taquerias[index].lock = SchemaTaqueriaLockState::default();
+        Ok(())
+    }
+
+    fn claim(&mut self, index: usize, height: u64) -> Result<u128> {
+        self.refresh(index, height)?;
+
+        let taqueria = &mut self.taquerias[index];
+        let amount = settle_claim(&self.global, &mut taqueria.emission)?;
+        taqueria.claimed += amount;
+        Ok(amount)
+    }
+
+    fn claim_all(&mut self, height: u64) -> Result<()> {
+        self.claim(0, height)?;
+        self.claim(1, height)?;
+        Ok(())
+    }
+
+    fn total_claimed(&self) -> u128 {
+        self.taquerias.iter().map(|t| t.claimed).sum()
+    }
+
+    fn emitted(&self) -> u128 {
+        self.height as u128 * TORTILLA_PER_BLOCK
+    }
+
+    fn assert_emission_conserved(&self) {
+        let emitted = self.emitted();
+        let claimed = self.total_claimed();
+
+        assert!(claimed <= emitted, "claimed {claimed} of {emitted} emitted");
+        assert!(
+            emitted - claimed < DUST,
+            "{} of {emitted} emitted tortilla was never paid out",
+            emitted - claimed
+        );
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_lock_boost_average_matches_per_block_sum() {
+    let unlock_height = MAX_LOCK_BLOCKS + 500;
+    let ranges = [
+        (0, 100),                                 //still at the full boost
+        (400, 900),                               //crosses into the decay
+        (1_000, 20_000),                          //decaying
+        (unlock_height - 50, unlock_height + 50), //crosses the unlock height
+        (unlock_height + 10, unlock_height + 20), //already unlocked
+    ];
+
+    for (from, to) in ranges {
+        //Same rounding as calc_lock_boost_average, one division over the summed remaining blocks
+        let remaining_sum: u128 = (from..to)
+            .filter(|height| *height < unlock_height)
+            .map(|height| (unlock_height - height).min(MAX_LOCK_BLOCKS) as u128)
+            .sum();
+        let expected = LOCK_AMOUNT * remaining_sum
+            / (to - from) as u128
+            / MAX_LOCK_BLOCKS as u128
+            / LOCK_TORTILLA_PER_WEIGHT;
+
+        assert_eq!(
+            calc_lock_boost_average(LOCK_AMOUNT, unlock_height, from, to),
+            expected,
+            "average over {from}..{to}"
+        );
+    }
+
+    //An empty range is just the boost at that height
+    assert_eq!(
+        calc_lock_boost_average(LOCK_AMOUNT, unlock_height, 600, 600),
+        calc_lock_boost(LOCK_AMOUNT, unlock_height, 600)
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_lock_boost_decays_to_zero_at_unlock() {
+    assert_eq!(calc_lock_boost(LOCK_AMOUNT, MAX_LOCK_BLOCKS, 0), 10);
+    assert_eq!(
+        calc_lock_boost(LOCK_AMOUNT, MAX_LOCK_BLOCKS, MAX_LOCK_BLOCKS / 2),
+        5
+    );
+    assert_eq!(
+        calc_lock_boost(LOCK_AMOUNT, MAX_LOCK_BLOCKS, MAX_LOCK_BLOCKS),
+        0
+    );
+
+    //Anything past MAX_LOCK_BLOCKS out is capped at the full boost
+    assert_eq!(calc_lock_boost(LOCK_AMOUNT, MAX_LOCK_BLOCKS * 2, 0), 10);
+}
+
+#[wasm_bindgen_test]
+fn test_overcredit_is_zero_until_blocks_pass() {
+    let lock = SchemaTaqueriaLockState {
+        amount: LOCK_AMOUNT,
+        unlock_height: 1_000 + MAX_LOCK_BLOCKS,
+        boost_weight: 10,
+        boost_height: 1_000,
+        boost_acc: 500,
+    };
+
+    assert_eq!(calc_lock_boost_overcredit(&lock, 1_000, 900), 0);
+
+    //Still at the full boost for the first block, nothing was overpaid
+    assert_eq!(calc_lock_boost_overcredit(&lock, 1_001, 900), 0);
+
+    //Half way the average boost is ~7.5, so ~2.5 weight was overpaid per unit of acc
+    let height = 1_000 + MAX_LOCK_BLOCKS / 2;
+    let average = calc_lock_boost_average(LOCK_AMOUNT, lock.unlock_height, 1_000, height);
+    assert_eq!(average, 7);
+    assert_eq!(
+        calc_lock_boost_overcredit(&lock, height, 900),
+        (10 - average) * 400
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_lock_raises_weight_and_unlock_restores_it() -> Result<()> {
+    let mut game = Game::new();
+
+    game.lock(0, 10, LOCK_AMOUNT, MAX_LOCK_BLOCKS)?;
+    assert_eq!(game.taquerias[0].emission.taqueria_weight, 11);
+    assert_eq!(game.global.total_weight, 12);
+
+    let unlock_height = 10 + MAX_LOCK_BLOCKS;
+    game.unlock(0, unlock_height)?;
+    assert_eq!(game.taquerias[0].emission.taqueria_weight, 1);
+    assert_eq!(game.global.total_weight, 2);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_poke_decays_the_weight() -> Result<()> {
+    let mut game = Game::new();
+    game.lock(0, 0, LOCK_AMOUNT, MAX_LOCK_BLOCKS)?;
+
+    game.poke(0, MAX_LOCK_BLOCKS / 2)?;
+    assert_eq!(game.taquerias[0].lock.boost_weight, 5);
+    assert_eq!(game.global.total_weight, 7);
+
+    //Poking twice in a block changes nothing
+    let acc = game.global.acc_reward_per_weight;
+    game.poke(0, MAX_LOCK_BLOCKS / 2)?;
+    assert_eq!(game.global.acc_reward_per_weight, acc);
+    assert_eq!(game.global.total_weight, 7);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_untouched_lock_is_settled_at_decayed_boost_and_nothing_is_lost() -> Result<()> {
+    let mut game = Game::new();
+    game.lock(0, 0, LOCK_AMOUNT, MAX_LOCK_BLOCKS)?;
+
+    //Nobody touches the lock for its whole life
+    let end = MAX_LOCK_BLOCKS + 100;
+    game.unlock(0, end)?;
+    game.claim_all(end)?;
+
+    game.assert_emission_conserved();
+
+    //Paid at the starting boost the locked taqueria would take 11/12 of every block while locked
+    let locked_emission = MAX_LOCK_BLOCKS as u128 * TORTILLA_PER_BLOCK;
+    assert!(game.taquerias[0].claimed < locked_emission * 11 / 12);
+    assert!(game.taquerias[0].claimed > game.taquerias[1].claimed);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_claims_across_blocks_with_pokes_conserve_emission() -> Result<()> {
+    let mut game = Game::new();
+    game.lock(0, 5, LOCK_AMOUNT, MAX_LOCK_BLOCKS)?;
+
+    let mut height = 5;
+    for round in 0..40u64 {
+        height += 997;
+
+        match round % 4 {
+            0 => game.poke(0, height)?,
+            1 => {
+                game.claim(0, height)?;
+            }
+            2 => {
+                game.claim(1, height)?;
+            }
+            _ => game.lock(0, height, LOCK_AMOUNT / 10, MIN_LOCK_BLOCKS)?,
+        }
+    }
+
+    let end = game.taquerias[0].lock.unlock_height + 1;
+    game.unlock(0, end)?;
+    game.claim_all(end)?;
+
+    game.assert_emission_conserved();
+    assert_eq!(game.global.total_weight, 2);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn test_claim_pays_nothing_twice() -> Result<()> {
+    let mut game = Game::new();
+    game.lock(0, 0, LOCK_AMOUNT, MAX_LOCK_BLOCKS)?;
+
+    assert!(game.claim(0, 100)? > 0);
+    assert_eq!(game.claim(0, 100)?, 0);
+
+    Ok(())
+}
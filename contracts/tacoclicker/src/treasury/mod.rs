@@ -0,0 +1,2 @@
+pub mod responder;
+pub mod schemas;
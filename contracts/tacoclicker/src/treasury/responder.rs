@@ -0,0 +1,200 @@
+use crate::consts::{TREASURY_DISTRIBUTION_INTERVAL, TREASURY_MAX_DISTRIBUTION};
+use crate::game::schemas::SchemaGlobalEmissionState;
+use crate::treasury::schemas::{
+    SchemaDistributeTreasuryResponse, SchemaTreasuryState, SchemaTreasuryView,
+    TreasuryInflowSource, TreasuryPolicy,
+};
+use crate::utils::encoders::decode_from_vec;
+use crate::Tortilla;
+use alkanes_runtime::{runtime::AlkaneResponder, storage::StoragePointer};
+use alkanes_support::response::CallResponse;
+use anyhow::{ensure, Context, Result};
+use borsh::BorshDeserialize;
+use metashrew_support::index_pointer::KeyValuePointer;
+use std::sync::Arc;
+
+//STORAGE GETTERS
+impl Tortilla {
+    pub fn get_treasury_pointer(&self) -> StoragePointer {
+        StoragePointer::from_keyword("/treasury")
+    }
+
+    pub fn get_treasury_state(&self) -> Result<SchemaTreasuryState> {
+        let bytes = (*self.get_treasury_pointer().get()).clone();
+        decode_from_vec!(bytes, SchemaTreasuryState)
+            .context("TORTILLA: failed to decode treasury state")
+    }
+
+    pub fn create_treasury(&self, policy: TreasuryPolicy) -> Result<()> {
+        let treasury = SchemaTreasuryState {
+            policy,
+            inflow_upgrades: 0u128,
+            inflow_lost_bets: 0u128,
+            inflow_airdrop_sweep: 0u128,
+            held: 0u128,
+            burned: 0u128,
+            pending_redistribution: 0u128,
+            redistributed: 0u128,
+            last_distribution_height: 0u64,
+        };
+
+        self.get_treasury_pointer().set(Arc::new(
+            borsh::to_vec(&treasury).context("TORTILLA: failed to encode initial treasury")?,
+        ));
+
+        Ok(())
+    }
+
+    //minted = false for tortilla that was forfeited before ever being minted (ie: lost bets)
+    pub fn record_treasury_inflow(
+        &self,
+        source: TreasuryInflowSource,
+        amount: u128,
+        minted: bool,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let mut treasury = self.get_treasury_state()?;
+
+        let inflow = match source {
+            TreasuryInflowSource::Upgrades => &mut treasury.inflow_upgrades,
+            TreasuryInflowSource::LostBets => &mut treasury.inflow_lost_bets,
+            TreasuryInflowSource::AirdropSweep => &mut treasury.inflow_airdrop_sweep,
+        };
+        *inflow = inflow.saturating_add(amount);
+
+        match treasury.policy {
+            TreasuryPolicy::Burn => {
+                if minted {
+                    treasury.burned = treasury.burned.saturating_add(amount);
+                }
+            }
+            TreasuryPolicy::Hold => {
+                if minted {
+                    treasury.held = treasury.held.saturating_add(amount);
+                }
+            }
+            TreasuryPolicy::Redistribute => {
+                //Minted tortilla is taken out of circulation so re-emitting it through the
+                //accumulator does not inflate the circulating supply
+                if minted {
+                    treasury.burned = treasury.burned.saturating_add(amount);
+                }
+                treasury.pending_redistribution =
+                    treasury.pending_redistribution.saturating_add(amount);
+            }
+        }
+
+        //Minted tortilla the policy doesnt hold is burned for real, taking it out of total supply
+        if minted && treasury.policy != TreasuryPolicy::Hold {
+            let consts = self.get_consts_value()?;
+            self.controlled_mint_contract_burn(consts.tortilla_alkane_id.into(), amount)?;
+        }
+
+        self.get_treasury_pointer()
+            .set(Arc::new(borsh::to_vec(&treasury)?));
+
+        Ok(())
+    }
+}
+
+impl Tortilla {
+    pub fn get_treasury(&self) -> Result<CallResponse> {
+        let ctx = self.context()?;
+        let mut response = CallResponse::forward(&ctx.incoming_alkanes);
+        let consts = self.get_consts_value()?;
+
+        let treasury = self.get_treasury_state()?;
+        let tortilla_total_supply =
+            self.controlled_mint_contract_get_total_supply(consts.tortilla_alkane_id.into())?;
+
+        let total_locked = self.get_total_locked_tortilla();
+
+        //Burned tortilla has already left the total supply, held and locked tortilla can't move
+        let circulating_supply = tortilla_total_supply
+            .saturating_sub(treasury.held)
+            .saturating_sub(total_locked);
+
+        response.data = borsh::to_vec(&SchemaTreasuryView {
+            treasury,
+            tortilla_total_supply,
+            circulating_supply,
+        })?;
+
+        Ok(response)
+    }
+
+    //Permissionless, shares pending treasury tortilla pro rata by weight like a dividend
+    pub fn distribute_treasury(&self) -> Result<CallResponse> {
+        let ctx = self.context()?;
+        let mut response = CallResponse::forward(&ctx.incoming_alkanes);
+
+        let mut treasury = self.get_treasury_state()?;
+        let current_height = self.height();
+
+        ensure!(
+            treasury.pending_redistribution > 0,
+            "TORTILLA: nothing in the treasury to distribute"
+        );
+        ensure!(
+            treasury.last_distribution_height == 0
+                || current_height
+                    >= treasury.last_distribution_height + TREASURY_DISTRIBUTION_INTERVAL,
+            "TORTILLA: treasury can only be distributed every {TREASURY_DISTRIBUTION_INTERVAL} blocks"
+        );
+
+        //Settle emissions up to this block before touching the accumulator
+        self.update_global()?;
+
+        let mut ptr_global_emission_state = self.get_global_emission_state_pointer();
+        let global_emission_state_bytes = (*ptr_global_emission_state.get()).clone();
+        let mut global: SchemaGlobalEmissionState =
+            decode_from_vec!(global_emission_state_bytes, SchemaGlobalEmissionState)?;
+
+        ensure!(
+            global.total_weight > 0,
+            "TORTILLA: no weight to distribute the treasury to"
+        );
+
+        //Bound a single distribution so the accumulator (and weight * acc debt math) cant overflow
+        let to_distribute = treasury
+            .pending_redistribution
+            .min(TREASURY_MAX_DISTRIBUTION);
+        let delta = to_distribute / global.total_weight;
+
+        ensure!(
+            delta > 0,
+            "TORTILLA: treasury balance too small to distribute across total weight"
+        );
+
+        //Whatever doesnt divide evenly across the weight stays pending for the next round
+        let distributed = delta
+            .checked_mul(global.total_weight)
+            .context("TORTILLA: overflow calculating distributed amount")?;
+
+        global.acc_reward_per_weight = global
+            .acc_reward_per_weight
+            .checked_add(delta)
+            .context("TORTILLA: accumulator overflow while distributing treasury")?;
+
+        treasury.pending_redistribution = treasury
+            .pending_redistribution
+            .checked_sub(distributed)
+            .context("TORTILLA: underflow while distributing treasury")?;
+        treasury.redistributed = treasury.redistributed.saturating_add(distributed);
+        treasury.last_distribution_height = current_height;
+
+        ptr_global_emission_state.set(Arc::new(borsh::to_vec(&global)?));
+        self.get_treasury_pointer()
+            .set(Arc::new(borsh::to_vec(&treasury)?));
+
+        response.data = borsh::to_vec(&SchemaDistributeTreasuryResponse {
+            distributed,
+            remaining: treasury.pending_redistribution,
+        })?;
+
+        Ok(response)
+    }
+}
@@ -0,0 +1,41 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, Copy)]
+pub enum TreasuryPolicy {
+    Burn,
+    Hold,
+    Redistribute,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, Copy)]
+pub enum TreasuryInflowSource {
+    Upgrades,
+    LostBets,
+    AirdropSweep,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct SchemaTreasuryState {
+    pub policy: TreasuryPolicy,
+    pub inflow_upgrades: u128,
+    pub inflow_lost_bets: u128,
+    pub inflow_airdrop_sweep: u128,
+    pub held: u128, //Minted TORTILLA sitting in the contract that the treasury can still spend
+    pub burned: u128, //Minted TORTILLA burned through the controlled mint, gone from total supply
+    pub pending_redistribution: u128, //Waiting to be pushed into the emission accumulator
+    pub redistributed: u128,
+    pub last_distribution_height: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct SchemaTreasuryView {
+    pub treasury: SchemaTreasuryState,
+    pub tortilla_total_supply: u128,
+    pub circulating_supply: u128,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct SchemaDistributeTreasuryResponse {
+    pub distributed: u128,
+    pub remaining: u128,
+}
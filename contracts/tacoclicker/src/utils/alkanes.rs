@@ -1,7 +1,7 @@
 use crate::{schemas::SchemaAlkaneId, utils::encoders::bytes_to_u128_words, Tortilla};
 use alkanes_runtime::runtime::AlkaneResponder;
 use alkanes_support::id::AlkaneId;
-use alkanes_support::parcel::AlkaneTransferParcel;
+use alkanes_support::parcel::{AlkaneTransfer, AlkaneTransferParcel};
 use alkanes_support::{cellpack::Cellpack, response::CallResponse};
 
 use anyhow::{anyhow, Context, Result};
@@ -76,4 +76,52 @@ impl Tortilla {
 
         Ok(())
     }
+
+    //Sends amount of the controlled mint's own token from this contract's balance into its Burn opcode
+    pub fn controlled_mint_contract_burn(&self, target: AlkaneId, amount: u128) -> Result<()> {
+        let cellpack = Cellpack {
+            target: target,
+            inputs: vec![117u128],
+        };
+
+        let alkanes = AlkaneTransferParcel(vec![AlkaneTransfer {
+            id: target,
+            value: amount,
+        }]);
+
+        self.call(&cellpack, &alkanes, self.fuel()).map_err(|e| {
+            anyhow!(
+                "TORTILLA: failed to burn alkane @ {},{} → {e}",
+                target.block,
+                target.tx
+            )
+        })?;
+
+        Ok(())
+    }
+
+    pub fn controlled_mint_contract_get_total_supply(&self, target: AlkaneId) -> Result<u128> {
+        let cellpack = Cellpack {
+            target: target,
+            inputs: vec![101u128],
+        };
+
+        let response = self
+            .staticcall(&cellpack, &AlkaneTransferParcel::default(), self.fuel())
+            .map_err(|e| {
+                anyhow!(
+                    "TORTILLA: failed to get total supply of alkane @ {},{} → {e}",
+                    target.block,
+                    target.tx
+                )
+            })?;
+
+        let bytes: [u8; 16] = response
+            .data
+            .get(..16)
+            .and_then(|b| b.try_into().ok())
+            .context("TORTILLA: total supply response is not a u128")?;
+
+        Ok(u128::from_le_bytes(bytes))
+    }
 }
@@ -19,6 +19,8 @@ import {
   IMerkleTree,
   schemaInitializeMerkleDistributorParameters,
   schemaMerkleProof,
+  schemaClaimedBitmapParameters,
+  schemaClaimedBitmapView,
 } from "./schemas";
 import {
   BoxedError,
@@ -75,6 +77,11 @@ const MerkleDistributorABI = TokenABI.extend({
     .execute(undefined, schemaMerkleProof)
     .returns("uint8Array"),
 
+  getClaimedBitmap: abi
+    .opcode(110n)
+    .view(schemaClaimedBitmapParameters)
+    .returns(schemaClaimedBitmapView),
+
   getMerkleProofForAddress: abi.opcode(999n).custom(async function (
     this: AlkanesBaseContract,
     opcode,
@@ -95,8 +102,10 @@ const MerkleDistributorABI = TokenABI.extend({
         );
       }
 
-      const { leaf, proofs } = merkleTree[params.address];
+      const { version, leaf, proofs } = merkleTree[params.address];
       const proof: IMerkleProof = {
+        // Trees published before the tool wrote a version are legacy v1 trees
+        version: version ?? 1,
         leaf: Array.from(Buffer.from(leaf, "hex")),
         proofs: proofs.map((p) => Array.from(Buffer.from(p, "hex"))),
       };
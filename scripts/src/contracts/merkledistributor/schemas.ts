@@ -1,26 +1,64 @@
 import { BorshSchema, Infer as BorshInfer } from "borsher";
 import { schemaAlkaneId } from "tacoclicker-sdk";
 
-export const schemaInitializeMerkleDistributorParameters = BorshSchema.Struct({
-  merkle_root: BorshSchema.Vec(BorshSchema.u8),
+export const schemaDistributorDeposit = BorshSchema.Struct({
   alkane_id: schemaAlkaneId,
   amount: BorshSchema.u128,
-  block_end: BorshSchema.u64,
 });
 
+// Leaves refer to deposits by their position in this vector
+export const schemaInitializeMerkleDistributorParameters = BorshSchema.Struct({
+  merkle_root: BorshSchema.Vec(BorshSchema.u8),
+  deposits: BorshSchema.Vec(schemaDistributorDeposit),
+  block_end: BorshSchema.u128,
+  allow_v1_proofs: BorshSchema.bool,
+  // Owner instead of the caller, required when initializing straight from a transaction
+  owner_address: BorshSchema.Option(BorshSchema.String),
+});
+
+// version 1 is the legacy untagged tree, version 2 uses tagged leaf and node hashes
 export const schemaMerkleProof = BorshSchema.Struct({
+  version: BorshSchema.u8,
   leaf: BorshSchema.Vec(BorshSchema.u8),
   proofs: BorshSchema.Vec(BorshSchema.Vec(BorshSchema.u8)),
 });
 
+export const schemaLeafAmount = BorshSchema.Struct({
+  asset_index: BorshSchema.u32,
+  amount: BorshSchema.u128,
+});
+
+// index is the leaf's position in the tree, it is the claim's slot in the claimed bitmap
 export const schemaMerkleLeaf = BorshSchema.Struct({
+  index: BorshSchema.u32,
   address: BorshSchema.String,
-  amount: BorshSchema.u128,
+  amounts: BorshSchema.Vec(schemaLeafAmount),
+});
+
+// 110  GetClaimedBitmap
+export const schemaClaimedBitmapParameters = BorshSchema.Struct({
+  start_word: BorshSchema.u32,
+  count: BorshSchema.u32,
 });
 
+// Word n covers leaf indices n*256..n*256+255, bit i of byte j is index n*256 + j*8 + i
+export const schemaClaimedBitmapView = BorshSchema.Struct({
+  start_word: BorshSchema.u32,
+  words: BorshSchema.Vec(BorshSchema.Array(BorshSchema.u8, 32)),
+});
+
+export type IDistributorDeposit = BorshInfer<typeof schemaDistributorDeposit>;
+export type ILeafAmount = BorshInfer<typeof schemaLeafAmount>;
 export type IMerkleLeaf = BorshInfer<typeof schemaMerkleLeaf>;
 export type IMerkleProof = BorshInfer<typeof schemaMerkleProof>;
+export type IClaimedBitmapParameters = BorshInfer<
+  typeof schemaClaimedBitmapParameters
+>;
+export type IClaimedBitmapView = BorshInfer<typeof schemaClaimedBitmapView>;
 export type IInitializeMerkleDistributorParameters = BorshInfer<
   typeof schemaInitializeMerkleDistributorParameters
 >;
-export type IMerkleTree = Record<string, { leaf: string; proofs: string[] }>;
+export type IMerkleTree = Record<
+  string,
+  { version?: number; leaf: string; proofs: string[] }
+>;
@@ -30,6 +30,13 @@ import {
   IMerkleTree,
   schemaMerkleProof,
   IMerkleProof,
+  schemaAirdropLeafParams,
+  schemaAirdropVestingView,
+  schemaAirdropClaimCheckParams,
+  schemaAirdropRoundParams,
+  schemaAddAirdropRoundParams,
+  schemaAirdropStatsView,
+  schemaAirdropRoundCount,
 } from "./schemas";
 
 import { Infer as BorshInfer } from "borsher";
@@ -84,13 +91,43 @@ async function getMerkleTree(
 const MerkleDistributorABI = TokenABI.extend({
   getIsValidAirdropClaim: abi
     .opcode(120n)
-    .view(schemaMerkleProof)
+    .view(schemaAirdropClaimCheckParams)
     .returns("bigint"),
   claimAirdrop: abi
     .opcode(121n)
-    .execute(undefined, schemaMerkleProof)
+    .execute(schemaAirdropRoundParams, schemaMerkleProof)
     .returns("uint8Array"),
 
+  claimVested: abi
+    .opcode(128n)
+    .execute(schemaAirdropLeafParams)
+    .returns("uint8Array"),
+
+  getAirdropVesting: abi
+    .opcode(129n)
+    .view(schemaAirdropLeafParams)
+    .returns(schemaAirdropVestingView),
+
+  sweepAirdrop: abi
+    .opcode(130n)
+    .execute(schemaAirdropRoundParams)
+    .returns("uint8Array"),
+
+  getAirdropStats: abi
+    .opcode(131n)
+    .view(schemaAirdropRoundParams)
+    .returns(schemaAirdropStatsView),
+
+  addAirdropRound: abi
+    .opcode(132n)
+    .execute(schemaAddAirdropRoundParams)
+    .returns("uint8Array"),
+
+  getAirdropRoundCount: abi
+    .opcode(133n)
+    .view()
+    .returns(schemaAirdropRoundCount),
+
   getMerkleProofForAddress: abi.opcode(999n).custom(async function (
     this: AlkanesBaseContract,
     opcode,
@@ -111,8 +148,10 @@ const MerkleDistributorABI = TokenABI.extend({
         );
       }
 
-      const { leaf, proofs } = merkleTree[params.address];
+      const { version, leaf, proofs } = merkleTree[params.address];
       const proof: IMerkleProof = {
+        // Trees published before the tool wrote a version are legacy v1 trees
+        version: version ?? 1,
         leaf: Array.from(Buffer.from(strip0x(leaf), "hex")),
         proofs: proofs.map((p) => Array.from(Buffer.from(strip0x(p), "hex"))),
       };
@@ -1,15 +1,32 @@
 import { schemaAlkaneId } from "tacoclicker-sdk";
 import { BorshSchema, Infer as BorshInfer } from "borsher";
 
+export const schemaTreasuryPolicy = BorshSchema.Enum({
+  Burn: BorshSchema.Unit,
+  Hold: BorshSchema.Unit,
+  Redistribute: BorshSchema.Unit,
+});
+export type ITreasuryPolicy = BorshInfer<typeof schemaTreasuryPolicy>;
+
+export const schemaVestingSchedule = BorshSchema.Struct({
+  cliff_blocks: BorshSchema.u64,
+  vesting_blocks: BorshSchema.u64,
+});
+export type IVestingSchedule = BorshInfer<typeof schemaVestingSchedule>;
+
 export const schemaTacoClickerInitializeParams = BorshSchema.Struct({
   controlled_mint_factory: schemaAlkaneId,
   merkle_root_id: BorshSchema.u8,
+  treasury_policy: schemaTreasuryPolicy,
+  airdrop_vesting: schemaVestingSchedule,
 });
 
 export const schemaTacoClickerConsts = BorshSchema.Struct({
   controlled_mint_factory: schemaAlkaneId,
   tortilla_alkane_id: schemaAlkaneId,
-  merkle_distributor_alkane_id: schemaAlkaneId,
+  admin_alkane_id: schemaAlkaneId,
+  merkle_root_id: BorshSchema.u8,
+  airdrop_vesting: schemaVestingSchedule,
 });
 
 export type IAlkaneId = BorshInfer<typeof schemaAlkaneId>;
@@ -147,7 +164,9 @@ export const schemaGlobalState = BorshSchema.Struct({
   salsa_state: schemaGlobalSalsaState,
 });
 
+// version 1 is the legacy untagged tree, version 2 uses tagged leaf and node hashes
 export const schemaMerkleProof = BorshSchema.Struct({
+  version: BorshSchema.u8,
   leaf: BorshSchema.Vec(BorshSchema.u8),
   proofs: BorshSchema.Vec(BorshSchema.Vec(BorshSchema.u8)),
 });
@@ -157,6 +176,71 @@ export const schemaMerkleLeaf = BorshSchema.Struct({
   amount: BorshSchema.u128,
 });
 export type IMerkleLeaf = BorshInfer<typeof schemaMerkleLeaf>;
+
+// 120  GetIsValidAirdropClaim
+export const schemaAirdropClaimCheckParams = BorshSchema.Struct({
+  round: BorshSchema.u32,
+  proof: schemaMerkleProof,
+});
+export type IAirdropClaimCheckParams = BorshInfer<
+  typeof schemaAirdropClaimCheckParams
+>;
+
+// 121  ClaimAirdrop, 130  SweepAirdrop, 131  GetAirdropStats, 134  ClaimAirdropSigned
+export const schemaAirdropRoundParams = BorshSchema.Struct({
+  round: BorshSchema.u32,
+});
+export type IAirdropRoundParams = BorshInfer<typeof schemaAirdropRoundParams>;
+
+// 132  AddAirdropRound (admin alkane only)
+export const schemaAddAirdropRoundParams = BorshSchema.Struct({
+  merkle_root: BorshSchema.Vec(BorshSchema.u8),
+  total: BorshSchema.u128,
+  start_height: BorshSchema.u64,
+  end_height: BorshSchema.u64,
+  allow_v1_proofs: BorshSchema.bool,
+});
+export type IAddAirdropRoundParams = BorshInfer<
+  typeof schemaAddAirdropRoundParams
+>;
+
+// 133  GetAirdropRoundCount
+export const schemaAirdropRoundCount = BorshSchema.u32;
+
+export const schemaAirdropRound = BorshSchema.Struct({
+  merkle_root: BorshSchema.Vec(BorshSchema.u8),
+  total: BorshSchema.u128,
+  claimed: BorshSchema.u128,
+  swept: BorshSchema.u128,
+  start_height: BorshSchema.u64,
+  end_height: BorshSchema.u64,
+  allow_v1_proofs: BorshSchema.bool,
+});
+
+export const schemaAirdropStatsView = BorshSchema.Struct({
+  round: schemaAirdropRound,
+  unclaimed: BorshSchema.u128,
+  expired: BorshSchema.u128,
+});
+export type IAirdropStatsView = BorshInfer<typeof schemaAirdropStatsView>;
+
+// 128  ClaimVested / 129  GetAirdropVesting
+export const schemaAirdropLeafParams = BorshSchema.Struct({
+  round: BorshSchema.u32,
+  leaf: BorshSchema.Vec(BorshSchema.u8),
+});
+export type IAirdropLeafParams = BorshInfer<typeof schemaAirdropLeafParams>;
+
+export const schemaAirdropVestingView = BorshSchema.Struct({
+  total: BorshSchema.u128,
+  vested: BorshSchema.u128,
+  claimed: BorshSchema.u128,
+  remaining: BorshSchema.u128,
+});
+export type IAirdropVestingView = BorshInfer<typeof schemaAirdropVestingView>;
 export type IMerkleProof = BorshInfer<typeof schemaMerkleProof>;
 
-export type IMerkleTree = Record<string, { leaf: string; proofs: string[] }>;
+export type IMerkleTree = Record<
+  string,
+  { version?: number; leaf: string; proofs: string[] }
+>;
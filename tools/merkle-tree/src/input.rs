@@ -0,0 +1,161 @@
+use anyhow::{anyhow, Context, Result};
+use bitcoin::{Address, Network};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AirdropEntry {
+    pub address: String,
+    //One amount per asset, in deposit order. TORTILLA leaves only ever have one
+    pub amounts: Vec<u128>,
+}
+
+//JSON amounts above 2^53 are only safe as strings, so both are accepted
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawAmount {
+    Number(u64),
+    Text(String),
+}
+
+impl RawAmount {
+    fn parse(self) -> Result<u128> {
+        match self {
+            RawAmount::Number(amount) => Ok(amount as u128),
+            RawAmount::Text(text) => parse_amount(&text),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct RawEntry {
+    address: String,
+    amount: Option<RawAmount>,
+    #[serde(default)]
+    amounts: Vec<RawAmount>,
+}
+
+fn parse_amount(raw: &str) -> Result<u128> {
+    raw.trim()
+        .replace('_', "")
+        .parse::<u128>()
+        .with_context(|| format!("MERKLE TREE: invalid amount \"{raw}\""))
+}
+
+fn read_json(path: &Path) -> Result<Vec<AirdropEntry>> {
+    let data = fs::read_to_string(path)
+        .with_context(|| format!("MERKLE TREE: could not read {}", path.display()))?;
+    let raw: Vec<RawEntry> = serde_json::from_str(&data).context(
+        "MERKLE TREE: expected a JSON array of { address, amount } or { address, amounts }",
+    )?;
+
+    raw.into_iter()
+        .map(|entry| {
+            let amounts = match (entry.amount, entry.amounts.is_empty()) {
+                (Some(amount), true) => vec![amount.parse()?],
+                (None, false) => entry
+                    .amounts
+                    .into_iter()
+                    .map(RawAmount::parse)
+                    .collect::<Result<Vec<_>>>()?,
+                _ => {
+                    return Err(anyhow!(
+                        "MERKLE TREE: {} needs exactly one of amount or amounts",
+                        entry.address
+                    ))
+                }
+            };
+            Ok(AirdropEntry {
+                address: entry.address.trim().to_string(),
+                amounts,
+            })
+        })
+        .collect()
+}
+
+//CSV rows are address,amount[,amount...]. A header row is skipped if its first amount isn't a number
+fn read_csv(path: &Path) -> Result<Vec<AirdropEntry>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .trim(csv::Trim::All)
+        .from_path(path)
+        .with_context(|| format!("MERKLE TREE: could not read {}", path.display()))?;
+
+    let mut entries = Vec::new();
+    for (row, record) in reader.records().enumerate() {
+        let record = record?;
+        let (address, first_amount) = match (record.get(0), record.get(1)) {
+            (Some(address), Some(amount)) => (address, amount),
+            _ => return Err(anyhow!("MERKLE TREE: row {} needs address,amount", row + 1)),
+        };
+
+        if row == 0 && parse_amount(first_amount).is_err() {
+            continue;
+        }
+
+        entries.push(AirdropEntry {
+            address: address.to_string(),
+            amounts: record
+                .iter()
+                .skip(1)
+                .map(parse_amount)
+                .collect::<Result<Vec<_>>>()?,
+        });
+    }
+
+    Ok(entries)
+}
+
+//Rejects anything the contract would refuse at claim time, so a bad row fails here instead of on-chain
+fn validate_entries(entries: &[AirdropEntry], network: Network) -> Result<()> {
+    let mut seen = HashSet::new();
+
+    for entry in entries {
+        Address::from_str(&entry.address)
+            .with_context(|| format!("MERKLE TREE: invalid address {}", entry.address))?
+            .require_network(network)
+            .with_context(|| {
+                format!(
+                    "MERKLE TREE: address {} is not a {network} address",
+                    entry.address
+                )
+            })?;
+
+        if entry.amounts.iter().all(|amount| *amount == 0) {
+            return Err(anyhow!("MERKLE TREE: {} has a zero amount", entry.address));
+        }
+
+        if !seen.insert(entry.address.as_str()) {
+            return Err(anyhow!(
+                "MERKLE TREE: {} appears more than once",
+                entry.address
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+pub fn read_entries(path: &Path, network: Network) -> Result<Vec<AirdropEntry>> {
+    let is_csv = path
+        .extension()
+        .map(|ext| ext.eq_ignore_ascii_case("csv"))
+        .unwrap_or(false);
+
+    let entries = if is_csv {
+        read_csv(path)?
+    } else {
+        read_json(path)?
+    };
+
+    if entries.is_empty() {
+        return Err(anyhow!("MERKLE TREE: {} has no entries", path.display()));
+    }
+
+    validate_entries(&entries, network)?;
+
+    Ok(entries)
+}
@@ -0,0 +1,12 @@
+pub mod input;
+pub mod output;
+pub mod tree;
+
+#[cfg(test)]
+mod tests;
+
+pub use input::{read_entries, AirdropEntry};
+pub use tree::{
+    decode_leaf, encode_leaf, encode_leaves, verify_multiproof, verify_proof, LeafFormat,
+    MerkleTree,
+};
@@ -0,0 +1,261 @@
+use anyhow::{anyhow, ensure, Context, Result};
+use bitcoin::Network;
+use clap::{Parser, Subcommand};
+use merkle_tree::output::{build_proof_files, write_outputs, ProofFile};
+use merkle_tree::{
+    decode_leaf, encode_leaves, read_entries, verify_multiproof, verify_proof, LeafFormat,
+    MerkleTree,
+};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use tacoclicker::consts::get_merkle_root_from_id;
+
+#[derive(Parser)]
+#[command(
+    name = "merkle-tree",
+    about = "Build and verify TORTILLA airdrop merkle trees"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Build a tree from a CSV or JSON list of (address, amounts) and write the root and proofs
+    Build {
+        #[arg(long)]
+        input: PathBuf,
+        #[arg(long, default_value = "out")]
+        out: PathBuf,
+        /// 1 for the original untagged format, 2 for tagged leaf and node hashes
+        #[arg(long, default_value_t = 2)]
+        merkle_version: u8,
+        #[arg(long, default_value = "regtest")]
+        network: Network,
+        #[arg(long, default_value = "MERKLE_ROOT")]
+        const_name: String,
+        /// tortilla for airdrop rounds, distributor for MerkleDistributor leaves with an index
+        #[arg(long, default_value = "tortilla")]
+        leaf_format: LeafFormat,
+    },
+    /// Print one multiproof covering several addresses, for batched claims
+    Multiproof {
+        #[arg(long)]
+        input: PathBuf,
+        #[arg(long, value_delimiter = ',')]
+        addresses: Vec<String>,
+        #[arg(long, default_value_t = 2)]
+        merkle_version: u8,
+        #[arg(long, default_value = "regtest")]
+        network: Network,
+        #[arg(long, default_value = "distributor")]
+        leaf_format: LeafFormat,
+    },
+    /// Check proofs against a root using the contract's verification code
+    Verify {
+        /// A tree.json or a single per-address proof file
+        #[arg(long)]
+        proofs: PathBuf,
+        /// Root as hex, or omit and pass --network-id to use MERKLE_ROOTS
+        #[arg(long)]
+        root: Option<String>,
+        /// 0 for regtest, 1 for mainnet, same as the contract's merkle_root_id
+        #[arg(long)]
+        network_id: Option<u8>,
+        #[arg(long, default_value = "tortilla")]
+        leaf_format: LeafFormat,
+    },
+}
+
+fn build(
+    input: PathBuf,
+    out: PathBuf,
+    version: u8,
+    network: Network,
+    const_name: String,
+    format: LeafFormat,
+) -> Result<()> {
+    let entries = read_entries(&input, network)?;
+    let leaves = encode_leaves(format, &entries)?;
+
+    let tree = MerkleTree::build(version, leaves)?;
+    let root = tree.root();
+    let files = build_proof_files(&tree, &entries)?;
+
+    //Never write a tree the contract would reject
+    for (address, file) in &files {
+        let (leaf, proofs) = file.decode()?;
+        ensure!(
+            verify_proof(format, file.version, &root, &leaf, &proofs)?,
+            "MERKLE TREE: generated proof for {address} does not verify"
+        );
+    }
+
+    write_outputs(&out, &const_name, &tree, &files)?;
+
+    println!("root: {}", hex::encode(root));
+    println!("leaves: {}", entries.len());
+    let width = entries
+        .iter()
+        .map(|entry| entry.amounts.len())
+        .max()
+        .unwrap_or(0);
+    for asset_index in 0..width {
+        println!(
+            "total asset {asset_index}: {}",
+            entries
+                .iter()
+                .filter_map(|entry| entry.amounts.get(asset_index))
+                .sum::<u128>()
+        );
+    }
+    println!("wrote {}", out.display());
+
+    Ok(())
+}
+
+fn multiproof(
+    input: PathBuf,
+    addresses: Vec<String>,
+    version: u8,
+    network: Network,
+    format: LeafFormat,
+) -> Result<()> {
+    let entries = read_entries(&input, network)?;
+    let leaves = encode_leaves(format, &entries)?;
+
+    let mut indices = addresses
+        .iter()
+        .map(|address| {
+            entries
+                .iter()
+                .position(|entry| entry.address == *address)
+                .map(|index| index as u32)
+                .ok_or_else(|| anyhow!("MERKLE TREE: {address} is not in the airdrop"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    indices.sort_unstable();
+    indices.dedup();
+
+    let tree = MerkleTree::build(version, leaves)?;
+    let proofs = tree.multiproof(&indices)?;
+    let leaves: Vec<Vec<u8>> = indices
+        .iter()
+        .map(|index| tree.leaves[*index as usize].clone())
+        .collect();
+
+    //Never print a multiproof ClaimMultiple would reject
+    ensure!(
+        verify_multiproof(
+            version,
+            &tree.root(),
+            tree.leaves.len() as u32,
+            &indices,
+            &leaves,
+            &proofs
+        )?,
+        "MERKLE TREE: generated multiproof does not verify"
+    );
+
+    let out = serde_json::json!({
+        "version": version,
+        "leaf_count": tree.leaves.len(),
+        "indices": indices,
+        "leaves": leaves.iter().map(hex::encode).collect::<Vec<_>>(),
+        "proofs": proofs.iter().map(hex::encode).collect::<Vec<_>>(),
+    });
+    println!("{}", serde_json::to_string_pretty(&out)?);
+
+    Ok(())
+}
+
+fn verify(
+    proofs: PathBuf,
+    root: Option<String>,
+    network_id: Option<u8>,
+    format: LeafFormat,
+) -> Result<()> {
+    let root: [u8; 32] = match (root, network_id) {
+        (Some(hex_root), None) => hex::decode(hex_root.trim_start_matches("0x"))?
+            .try_into()
+            .map_err(|_| anyhow!("MERKLE TREE: root must be 32 bytes"))?,
+        (None, Some(id)) => get_merkle_root_from_id(id)?,
+        _ => {
+            return Err(anyhow!(
+                "MERKLE TREE: pass exactly one of --root or --network-id"
+            ))
+        }
+    };
+
+    let data = fs::read_to_string(&proofs)
+        .with_context(|| format!("MERKLE TREE: could not read {}", proofs.display()))?;
+
+    let files: BTreeMap<String, ProofFile> = match serde_json::from_str(&data) {
+        Ok(tree) => tree,
+        Err(_) => {
+            let file: ProofFile = serde_json::from_str(&data)
+                .context("MERKLE TREE: expected a tree.json or a proof file")?;
+            BTreeMap::from([(String::new(), file)])
+        }
+    };
+
+    let mut failed = 0;
+    for (key, file) in &files {
+        let (leaf_bytes, sibs) = file.decode()?;
+        let leaf = decode_leaf(format, &leaf_bytes)?;
+
+        let keyed_correctly = key.is_empty() || *key == leaf.address;
+        let valid =
+            keyed_correctly && verify_proof(format, file.version, &root, &leaf_bytes, &sibs)?;
+
+        if !valid {
+            failed += 1;
+        }
+        println!(
+            "{} {} {}",
+            if valid { "ok  " } else { "FAIL" },
+            leaf.address,
+            leaf.amounts
+                .iter()
+                .map(|amount| amount.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        );
+    }
+
+    ensure!(
+        failed == 0,
+        "MERKLE TREE: {failed} of {} proofs failed",
+        files.len()
+    );
+
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    match Cli::parse().command {
+        Command::Build {
+            input,
+            out,
+            merkle_version,
+            network,
+            const_name,
+            leaf_format,
+        } => build(input, out, merkle_version, network, const_name, leaf_format),
+        Command::Multiproof {
+            input,
+            addresses,
+            merkle_version,
+            network,
+            leaf_format,
+        } => multiproof(input, addresses, merkle_version, network, leaf_format),
+        Command::Verify {
+            proofs,
+            root,
+            network_id,
+            leaf_format,
+        } => verify(proofs, root, network_id, leaf_format),
+    }
+}
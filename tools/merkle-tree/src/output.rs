@@ -0,0 +1,89 @@
+use crate::input::AirdropEntry;
+use crate::tree::MerkleTree;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+//Same shape the scripts fetch as IMerkleTree, keyed by address
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProofFile {
+    pub version: u8,
+    pub leaf: String,
+    pub proofs: Vec<String>,
+}
+
+impl ProofFile {
+    pub fn decode(&self) -> Result<(Vec<u8>, Vec<Vec<u8>>)> {
+        let leaf = hex::decode(&self.leaf).context("MERKLE TREE: leaf is not valid hex")?;
+        let proofs = self
+            .proofs
+            .iter()
+            .map(|sib| hex::decode(sib).context("MERKLE TREE: proof is not valid hex"))
+            .collect::<Result<Vec<_>>>()?;
+        Ok((leaf, proofs))
+    }
+}
+
+pub fn build_proof_files(
+    tree: &MerkleTree,
+    entries: &[AirdropEntry],
+) -> Result<BTreeMap<String, ProofFile>> {
+    let mut files = BTreeMap::new();
+
+    for (index, entry) in entries.iter().enumerate() {
+        let proofs = tree.proof(index)?;
+        files.insert(
+            entry.address.clone(),
+            ProofFile {
+                version: tree.version,
+                leaf: hex::encode(&tree.leaves[index]),
+                proofs: proofs.iter().map(hex::encode).collect(),
+            },
+        );
+    }
+
+    Ok(files)
+}
+
+//Formatted the way rustfmt lays out the byte arrays in consts.rs so it can be pasted straight in
+pub fn format_root_const(name: &str, root: &[u8; 32]) -> String {
+    let mut out = format!("pub const {name}: [u8; 32] = [\n");
+    for row in root.chunks(16) {
+        let bytes: Vec<String> = row.iter().map(|b| format!("0x{b:02x},")).collect();
+        out.push_str(&format!("    {}\n", bytes.join(" ")));
+    }
+    out.push_str("];\n");
+    out
+}
+
+pub fn write_outputs(
+    out_dir: &Path,
+    const_name: &str,
+    tree: &MerkleTree,
+    files: &BTreeMap<String, ProofFile>,
+) -> Result<()> {
+    let proofs_dir = out_dir.join("proofs");
+    fs::create_dir_all(&proofs_dir)
+        .with_context(|| format!("MERKLE TREE: could not create {}", proofs_dir.display()))?;
+
+    fs::write(
+        out_dir.join("root.rs"),
+        format_root_const(const_name, &tree.root()),
+    )?;
+    fs::write(out_dir.join("root.txt"), hex::encode(tree.root()))?;
+    fs::write(
+        out_dir.join("tree.json"),
+        serde_json::to_string_pretty(files)?,
+    )?;
+
+    for (address, file) in files {
+        fs::write(
+            proofs_dir.join(format!("{address}.json")),
+            serde_json::to_string_pretty(file)?,
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,2 @@
+mod multiproof;
+mod tree;
@@ -0,0 +1,123 @@
+use crate::input::AirdropEntry;
+use crate::tree::{encode_leaves, verify_multiproof, LeafFormat, MerkleTree};
+use merkle_distributor::utils::calc_merkle_multiproof_root;
+
+fn tree(version: u8, count: usize) -> MerkleTree {
+    let entries: Vec<AirdropEntry> = (0..count)
+        .map(|i| AirdropEntry {
+            address: format!("address-{i}"),
+            amounts: vec![1_000 + i as u128],
+        })
+        .collect();
+    MerkleTree::build(
+        version,
+        encode_leaves(LeafFormat::Distributor, &entries).unwrap(),
+    )
+    .unwrap()
+}
+
+fn leaves_at(tree: &MerkleTree, indices: &[u32]) -> Vec<Vec<u8>> {
+    indices
+        .iter()
+        .map(|index| tree.leaves[*index as usize].clone())
+        .collect()
+}
+
+fn verifies(tree: &MerkleTree, indices: &[u32]) -> bool {
+    let proofs = tree.multiproof(indices).unwrap();
+    verify_multiproof(
+        tree.version,
+        &tree.root(),
+        tree.leaves.len() as u32,
+        indices,
+        &leaves_at(tree, indices),
+        &proofs,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_every_subset_verifies_with_odd_layer_widths() {
+    for version in [1u8, 2] {
+        for count in 2..=9usize {
+            let tree = tree(version, count);
+
+            for mask in 1u32..(1 << count) {
+                let indices: Vec<u32> =
+                    (0..count as u32).filter(|i| mask & (1 << i) != 0).collect();
+                assert!(
+                    verifies(&tree, &indices),
+                    "v{version} multiproof for {indices:?} of {count} does not verify"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_adjacent_indices_need_no_sibling_for_each_other() {
+    let tree = tree(2, 4);
+
+    //0 and 1 hash together, only the node over 2 and 3 is supplied
+    let proofs = tree.multiproof(&[0, 1]).unwrap();
+    assert_eq!(proofs.len(), 1);
+    assert!(verifies(&tree, &[0, 1]));
+}
+
+#[test]
+fn test_duplicate_indices_are_rejected() {
+    let tree = tree(2, 5);
+    let proofs = tree.multiproof(&[1]).unwrap();
+
+    let result =
+        calc_merkle_multiproof_root(2, 5, &[1, 1], &leaves_at(&tree, &[1, 1]), &proofs, true);
+    assert!(result.is_err());
+    assert!(tree.multiproof(&[1, 1]).is_err());
+}
+
+#[test]
+fn test_missing_sibling_is_rejected() {
+    let tree = tree(2, 7);
+    let indices = [0, 5];
+    let mut proofs = tree.multiproof(&indices).unwrap();
+    proofs.pop();
+
+    let result =
+        calc_merkle_multiproof_root(2, 7, &indices, &leaves_at(&tree, &indices), &proofs, true);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_extra_sibling_is_rejected() {
+    let tree = tree(2, 7);
+    let indices = [0, 5];
+    let mut proofs = tree.multiproof(&indices).unwrap();
+    proofs.push(proofs[0].clone());
+
+    let result =
+        calc_merkle_multiproof_root(2, 7, &indices, &leaves_at(&tree, &indices), &proofs, true);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_wrong_leaf_count_does_not_verify() {
+    let tree = tree(2, 6);
+    let indices = [4, 5];
+    let proofs = tree.multiproof(&indices).unwrap();
+
+    //Claiming a wider tree moves where siblings are expected, so the root can't match
+    let result =
+        calc_merkle_multiproof_root(2, 7, &indices, &leaves_at(&tree, &indices), &proofs, true);
+    assert!(result.map(|root| root != tree.root()).unwrap_or(true));
+}
+
+#[test]
+fn test_v1_needs_allow_v1() {
+    let tree = tree(1, 4);
+    let indices = [2];
+    let proofs = tree.multiproof(&indices).unwrap();
+
+    let result =
+        calc_merkle_multiproof_root(1, 4, &indices, &leaves_at(&tree, &indices), &proofs, false);
+    assert!(result.is_err());
+}
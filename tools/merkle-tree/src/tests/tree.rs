@@ -0,0 +1,164 @@
+use crate::input::AirdropEntry;
+use crate::tree::{decode_leaf, encode_leaf, encode_leaves, verify_proof, LeafFormat, MerkleTree};
+use merkle_distributor::schemas::{SchemaLeafAmount, SchemaMerkleLeaf};
+
+//Odd counts exercise the carried up node at the end of a layer
+const LEAF_COUNTS: [usize; 8] = [1, 2, 3, 4, 5, 7, 8, 13];
+
+fn entries(count: usize) -> Vec<AirdropEntry> {
+    (0..count)
+        .map(|i| AirdropEntry {
+            address: format!("address-{i}"),
+            amounts: vec![1_000 + i as u128, i as u128],
+        })
+        .collect()
+}
+
+fn tree(version: u8, count: usize) -> MerkleTree {
+    let leaves = encode_leaves(LeafFormat::Distributor, &entries(count)).unwrap();
+    MerkleTree::build(version, leaves).unwrap()
+}
+
+#[test]
+fn test_every_proof_matches_contract_root() {
+    for version in [1u8, 2] {
+        for count in LEAF_COUNTS {
+            //v1 hashes the raw leaf into the first pair, a lone leaf never becomes a root
+            if version == 1 && count == 1 {
+                continue;
+            }
+
+            let tree = tree(version, count);
+            let root = tree.root();
+
+            for index in 0..count {
+                let proof = tree.proof(index).unwrap();
+                assert!(
+                    verify_proof(
+                        LeafFormat::Distributor,
+                        version,
+                        &root,
+                        &tree.leaves[index],
+                        &proof
+                    )
+                    .unwrap(),
+                    "v{version} proof for leaf {index} of {count} does not verify"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_tortilla_proofs_match_contract_root() {
+    let entries: Vec<AirdropEntry> = (0..5)
+        .map(|i| AirdropEntry {
+            address: format!("address-{i}"),
+            amounts: vec![1_000 + i as u128],
+        })
+        .collect();
+
+    for version in [1u8, 2] {
+        let leaves = encode_leaves(LeafFormat::Tortilla, &entries).unwrap();
+        let tree = MerkleTree::build(version, leaves).unwrap();
+
+        for index in 0..entries.len() {
+            let proof = tree.proof(index).unwrap();
+            assert!(verify_proof(
+                LeafFormat::Tortilla,
+                version,
+                &tree.root(),
+                &tree.leaves[index],
+                &proof
+            )
+            .unwrap());
+        }
+    }
+}
+
+#[test]
+fn test_single_leaf_v2_root_is_leaf_hash() {
+    let tree = tree(2, 1);
+    assert!(tree.proof(0).unwrap().is_empty());
+    assert!(verify_proof(
+        LeafFormat::Distributor,
+        2,
+        &tree.root(),
+        &tree.leaves[0],
+        &[]
+    )
+    .unwrap());
+}
+
+#[test]
+fn test_single_leaf_v1_is_rejected() {
+    let leaves = encode_leaves(LeafFormat::Distributor, &entries(1)).unwrap();
+    assert!(MerkleTree::build(1, leaves).is_err());
+}
+
+#[test]
+fn test_proof_does_not_verify_other_leaf() {
+    let tree = tree(2, 5);
+    let proof = tree.proof(0).unwrap();
+    assert!(!verify_proof(
+        LeafFormat::Distributor,
+        2,
+        &tree.root(),
+        &tree.leaves[1],
+        &proof
+    )
+    .unwrap());
+}
+
+#[test]
+fn test_distributor_leaf_uses_contract_schema() {
+    let entry = AirdropEntry {
+        address: "address-0".to_string(),
+        amounts: vec![5, 0, 7],
+    };
+
+    let encoded = encode_leaf(LeafFormat::Distributor, 3, &entry).unwrap();
+    let leaf: SchemaMerkleLeaf = borsh::from_slice(&encoded).unwrap();
+
+    //Zero amounts are left out, each amount keeps its column as the asset index
+    assert_eq!(
+        leaf,
+        SchemaMerkleLeaf {
+            index: 3,
+            address: "address-0".to_string(),
+            amounts: vec![
+                SchemaLeafAmount {
+                    asset_index: 0,
+                    amount: 5,
+                },
+                SchemaLeafAmount {
+                    asset_index: 2,
+                    amount: 7,
+                },
+            ],
+        }
+    );
+
+    let decoded = decode_leaf(LeafFormat::Distributor, &encoded).unwrap();
+    assert_eq!(decoded.address, entry.address);
+    assert_eq!(decoded.amounts, entry.amounts);
+}
+
+#[test]
+fn test_tortilla_leaf_round_trips() {
+    let entry = AirdropEntry {
+        address: "address-0".to_string(),
+        amounts: vec![42],
+    };
+
+    let encoded = encode_leaf(LeafFormat::Tortilla, 0, &entry).unwrap();
+    let decoded = decode_leaf(LeafFormat::Tortilla, &encoded).unwrap();
+    assert_eq!(decoded.address, entry.address);
+    assert_eq!(decoded.amounts, entry.amounts);
+
+    let multi = AirdropEntry {
+        address: "address-1".to_string(),
+        amounts: vec![1, 2],
+    };
+    assert!(encode_leaf(LeafFormat::Tortilla, 1, &multi).is_err());
+}
@@ -0,0 +1,250 @@
+use crate::input::AirdropEntry;
+use anyhow::{anyhow, ensure, Context, Result};
+use borsh::BorshDeserialize;
+use merkle_distributor::schemas::{
+    SchemaLeafAmount as DistributorLeafAmount, SchemaMerkleLeaf as DistributorMerkleLeaf,
+};
+use merkle_distributor::utils::{
+    calc_merkle_multiproof_root, calc_merkle_root_for_version as distributor_merkle_root,
+};
+use merkle_support::{hash_merkle_pair, tagged_hash, MERKLE_LEAF_TAG};
+use std::str::FromStr;
+use tacoclicker::airdrop::schemas::SchemaMerkleLeaf;
+use tacoclicker::airdrop::utils::calc_merkle_root_for_version as tortilla_merkle_root;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LeafFormat {
+    //TORTILLA airdrop rounds: { address, amount }
+    Tortilla,
+    //MerkleDistributor: { index, address, amounts }, index is the leaf's position in the tree
+    //and each amount's asset_index is its column
+    Distributor,
+}
+
+impl FromStr for LeafFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "tortilla" => Ok(LeafFormat::Tortilla),
+            "distributor" => Ok(LeafFormat::Distributor),
+            _ => Err(anyhow!(
+                "MERKLE TREE: leaf format must be tortilla or distributor"
+            )),
+        }
+    }
+}
+
+pub fn encode_leaf(format: LeafFormat, index: u32, entry: &AirdropEntry) -> Result<Vec<u8>> {
+    Ok(match format {
+        LeafFormat::Tortilla => {
+            ensure!(
+                entry.amounts.len() == 1,
+                "MERKLE TREE: tortilla leaves take a single amount, {} has {}",
+                entry.address,
+                entry.amounts.len()
+            );
+            borsh::to_vec(&SchemaMerkleLeaf {
+                address: entry.address.clone(),
+                amount: entry.amounts[0],
+            })?
+        }
+        LeafFormat::Distributor => borsh::to_vec(&DistributorMerkleLeaf {
+            index,
+            address: entry.address.clone(),
+            amounts: entry
+                .amounts
+                .iter()
+                .enumerate()
+                .filter(|(_, amount)| **amount > 0)
+                .map(|(asset_index, amount)| DistributorLeafAmount {
+                    asset_index: asset_index as u32,
+                    amount: *amount,
+                })
+                .collect(),
+        })?,
+    })
+}
+
+pub fn decode_leaf(format: LeafFormat, bytes: &[u8]) -> Result<AirdropEntry> {
+    Ok(match format {
+        LeafFormat::Tortilla => {
+            let leaf = SchemaMerkleLeaf::try_from_slice(bytes)
+                .context("MERKLE TREE: leaf is not a borsh tortilla leaf")?;
+            AirdropEntry {
+                address: leaf.address,
+                amounts: vec![leaf.amount],
+            }
+        }
+        LeafFormat::Distributor => {
+            let leaf = DistributorMerkleLeaf::try_from_slice(bytes)
+                .context("MERKLE TREE: leaf is not a borsh distributor leaf")?;
+
+            let width = leaf
+                .amounts
+                .iter()
+                .map(|entry| entry.asset_index as usize + 1)
+                .max()
+                .unwrap_or(0);
+            let mut amounts = vec![0u128; width];
+            for entry in leaf.amounts {
+                amounts[entry.asset_index as usize] += entry.amount;
+            }
+
+            AirdropEntry {
+                address: leaf.address,
+                amounts,
+            }
+        }
+    })
+}
+
+pub fn encode_leaves(format: LeafFormat, entries: &[AirdropEntry]) -> Result<Vec<Vec<u8>>> {
+    entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| encode_leaf(format, index as u32, entry))
+        .collect()
+}
+
+pub struct MerkleTree {
+    pub version: u8,
+    pub leaves: Vec<Vec<u8>>,
+    //layers[0] holds the leaf nodes, the last layer holds only the root
+    layers: Vec<Vec<Vec<u8>>>,
+}
+
+impl MerkleTree {
+    pub fn build(version: u8, leaves: Vec<Vec<u8>>) -> Result<Self> {
+        ensure!(
+            !leaves.is_empty(),
+            "MERKLE TREE: cannot build a tree with no leaves"
+        );
+
+        let first_layer: Vec<Vec<u8>> = match version {
+            //v1 feeds the raw leaf into the first pair hash, so a lone leaf would never become a 32 byte root
+            1 => {
+                ensure!(
+                    leaves.len() > 1,
+                    "MERKLE TREE: v1 trees need at least two leaves"
+                );
+                leaves.clone()
+            }
+            2 => leaves
+                .iter()
+                .map(|leaf| tagged_hash(MERKLE_LEAF_TAG, &[leaf]).to_vec())
+                .collect(),
+            _ => return Err(anyhow!("MERKLE TREE: unknown merkle version {version}")),
+        };
+
+        let mut layers = vec![first_layer];
+
+        while layers[layers.len() - 1].len() > 1 {
+            let next = layers[layers.len() - 1]
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => hash_merkle_pair(version, a, b),
+                    //An odd node out is carried up unchanged and contributes no sibling
+                    [a] => a.clone(),
+                    _ => unreachable!(),
+                })
+                .collect();
+            layers.push(next);
+        }
+
+        Ok(Self {
+            version,
+            leaves,
+            layers,
+        })
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        let mut root = [0u8; 32];
+        root.copy_from_slice(&self.layers[self.layers.len() - 1][0]);
+        root
+    }
+
+    pub fn proof(&self, index: usize) -> Result<Vec<Vec<u8>>> {
+        ensure!(
+            index < self.leaves.len(),
+            "MERKLE TREE: leaf index {index} out of range"
+        );
+
+        let mut proofs = Vec::new();
+        let mut position = index;
+
+        for layer in &self.layers[..self.layers.len() - 1] {
+            let sibling = position ^ 1;
+            if sibling < layer.len() {
+                proofs.push(layer[sibling].clone());
+            }
+            position /= 2;
+        }
+
+        Ok(proofs)
+    }
+
+    //Siblings for proving several leaves together, in the order calc_merkle_multiproof_root consumes them
+    pub fn multiproof(&self, indices: &[u32]) -> Result<Vec<Vec<u8>>> {
+        ensure!(
+            !indices.is_empty() && indices.windows(2).all(|w| w[0] < w[1]),
+            "MERKLE TREE: multiproof indices must be non-empty and strictly increasing"
+        );
+        ensure!(
+            (indices[indices.len() - 1] as usize) < self.leaves.len(),
+            "MERKLE TREE: multiproof index out of range"
+        );
+
+        let mut proofs = Vec::new();
+        let mut known = indices.to_vec();
+
+        for layer in &self.layers[..self.layers.len() - 1] {
+            let mut next = Vec::with_capacity(known.len());
+            let mut i = 0;
+
+            while i < known.len() {
+                let sibling = known[i] ^ 1;
+                if i + 1 < known.len() && known[i + 1] == sibling {
+                    i += 1;
+                } else if (sibling as usize) < layer.len() {
+                    proofs.push(layer[sibling as usize].clone());
+                }
+                next.push(known[i] / 2);
+                i += 1;
+            }
+
+            known = next;
+        }
+
+        Ok(proofs)
+    }
+}
+
+//Runs the root calculation of the contract that will see this leaf format, v1 is always allowed
+//here since the tool may be checking old trees
+pub fn verify_proof(
+    format: LeafFormat,
+    version: u8,
+    root: &[u8; 32],
+    leaf: &[u8],
+    proofs: &[Vec<u8>],
+) -> Result<bool> {
+    let calc_root = match format {
+        LeafFormat::Tortilla => tortilla_merkle_root,
+        LeafFormat::Distributor => distributor_merkle_root,
+    };
+    Ok(calc_root(version, leaf, proofs, true)? == *root)
+}
+
+//Runs the distributor's multiproof reconstruction, which is what ClaimMultiple checks on-chain
+pub fn verify_multiproof(
+    version: u8,
+    root: &[u8; 32],
+    leaf_count: u32,
+    indices: &[u32],
+    leaves: &[Vec<u8>],
+    proofs: &[Vec<u8>],
+) -> Result<bool> {
+    Ok(calc_merkle_multiproof_root(version, leaf_count, indices, leaves, proofs, true)? == *root)
+}